@@ -0,0 +1,119 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::extract::input::PuzzleInput;
+use crate::transform::{day, solver::Part};
+
+/// Summary of a set of timings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            return Stats { min: Duration::ZERO, median: Duration::ZERO, mean: Duration::ZERO, stddev: Duration::ZERO };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let variance = seconds.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / seconds.len() as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timings of one part, with parsing measured apart from solving.
+pub struct Benchmark {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub runs: u32,
+    pub warmup: u32,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+/// Solves `part` of `input` `warmup` times without measuring, then `runs`
+/// times timing the parse and solve steps of each run.
+pub fn bench(input: &PuzzleInput, part: Part, warmup: u32, runs: u32) -> Result<Benchmark, AocError> {
+    let puzzle = day::find(input.year, input.day).ok_or(AocError::UnknownDay { year: input.year, day: input.day })?;
+    if !puzzle.parts().contains(&part) {
+        return Err(AocError::UnsupportedPart { day: input.day, part: part.number() });
+    }
+
+    let mut parse_samples = vec![];
+    let mut solve_samples = vec![];
+
+    for iteration in 0..warmup + runs {
+        let start = Instant::now();
+        let parsed = puzzle.parse(black_box(&input.text))?;
+        let parsed_at = Instant::now();
+        black_box(parsed.solve(part)?);
+        let solved_at = Instant::now();
+
+        if iteration >= warmup {
+            parse_samples.push(parsed_at - start);
+            solve_samples.push(solved_at - parsed_at);
+        }
+    }
+
+    let total_samples: Vec<Duration> = parse_samples.iter().zip(&solve_samples)
+        .map(|(parse, solve)| *parse + *solve)
+        .collect();
+
+    Ok(Benchmark {
+        year: input.year,
+        day: input.day,
+        part,
+        runs,
+        warmup,
+        parse: Stats::from_samples(&parse_samples),
+        solve: Stats::from_samples(&solve_samples),
+        total: Stats::from_samples(&total_samples),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples(){
+        let samples: Vec<Duration> = [4, 1, 3, 2].into_iter().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(2500), stats.median);
+        assert_eq!(Duration::from_micros(2500), stats.mean);
+        assert_eq!(1118, stats.stddev.as_micros());
+    }
+
+    #[test]
+    fn bench_times_every_run(){
+        let input = PuzzleInput { year: 2025, day: 1, name: "inline".to_string(), text: "L68\nL30\nR48".to_string() };
+        let benchmark = bench(&input, Part::Two, 1, 3).unwrap();
+        assert_eq!(3, benchmark.runs);
+        assert!(benchmark.total.min >= benchmark.solve.min);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{error::Error, fmt, io, path::PathBuf, time::Duration};
+
+/// Everything that can go wrong while extracting, solving or loading a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    /// The command line could not be turned into a valid `Config`.
+    BadArguments(String),
+    /// The input file for the requested day does not exist.
+    MissingInput(PathBuf),
+    /// The settings file asked for with `--config` or `AOC_CONFIG` does not exist.
+    MissingSettings(PathBuf),
+    /// The file that should hold the session token does not exist.
+    MissingSessionFile(PathBuf),
+    /// Any other failure while reading or writing a file.
+    Io(io::Error),
+    /// The puzzle input does not have the shape the solver expects. Lines and
+    /// columns are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// There is no solver registered for this day.
+    UnknownDay { year: u16, day: u8 },
+    /// The solver for this day does not have the requested part.
+    UnsupportedPart { day: u8, part: u8 },
+    /// The solver of this part is still the stub written by `new`.
+    Unwritten { day: u8, part: u8 },
+    /// An intermediate value does not fit in the integer type used by the solver.
+    Overflow(&'static str),
+    /// A request to the Advent of Code website failed or returned a page we
+    /// do not understand.
+    Http(String),
+    /// The solver panicked with this message.
+    Panicked(String),
+    /// The solver gave no answer within this time.
+    TimedOut(Duration),
+    /// An answer was not submitted because earlier verdicts show it cannot be
+    /// accepted.
+    Rejected(String),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, text: &str, message: impl Into<String>) -> AocError {
+        AocError::Parse {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Builds a parse error for `fragment`, which must be a slice of `source`,
+    /// working out its line and column from where it sits inside `source`.
+    pub fn parse_at(source: &str, fragment: &str, message: impl Into<String>) -> AocError {
+        let offset = (fragment.as_ptr() as usize)
+            .saturating_sub(source.as_ptr() as usize)
+            .min(source.len());
+        let before = source.get(..offset).unwrap_or_default();
+
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+
+        AocError::parse(line, column, fragment, message)
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::BadArguments(reason) => write!(f, "bad arguments: {reason}"),
+            AocError::MissingInput(path) => write!(f, "input file {} does not exist", path.display()),
+            AocError::MissingSettings(path) => write!(f, "settings file {} does not exist", path.display()),
+            AocError::MissingSessionFile(path) => write!(f, "session file {} does not exist", path.display()),
+            AocError::Io(err) => write!(f, "{err}"),
+            AocError::Parse { line, column, text, message } => {
+                write!(f, "line {line}, column {column}: {message} (found {:?})", excerpt(text))
+            }
+            AocError::UnknownDay { year, day } => write!(f, "no solution available for day {day} of {year}"),
+            AocError::UnsupportedPart { day, part } => write!(f, "day {day} has no part {part}"),
+            AocError::Unwritten { day, part } => write!(f, "part {part} of day {day} is not written yet"),
+            AocError::Overflow(context) => write!(f, "arithmetic overflow while {context}"),
+            AocError::Panicked(message) => write!(f, "the solver panicked: {message}"),
+            AocError::TimedOut(timeout) => write!(f, "no answer after {timeout:?}"),
+            AocError::Http(reason) => write!(f, "request to Advent of Code failed: {reason}"),
+            AocError::Rejected(reason) => write!(f, "not submitted: {reason}"),
+        }
+    }
+}
+
+/// Keeps error messages short when the offending text spans many lines.
+fn excerpt(text: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    let first_line = text.lines().next().unwrap_or_default();
+    if first_line.len() == text.len() && text.chars().count() <= MAX_CHARS {
+        text.to_string()
+    } else {
+        format!("{}...", first_line.chars().take(MAX_CHARS).collect::<String>())
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> AocError {
+        AocError::Io(err)
+    }
+}
+
+impl From<ureq::Error> for AocError {
+    fn from(err: ureq::Error) -> AocError {
+        match err {
+            ureq::Error::Status(code, response) => {
+                AocError::Http(format!("{} answered {code} {}", response.get_url(), response.status_text()))
+            }
+            ureq::Error::Transport(transport) => AocError::Http(transport.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_at_locates_fragment(){
+        let source = "3-5\n10-14\n\n1\n5a";
+        let fragment = &source[13..15];
+        assert!(matches!(AocError::parse_at(source, fragment, "bad id"), AocError::Parse { line: 5, column: 1, .. }));
+        assert!(matches!(AocError::parse_at(source, &source[6..], "bad id"), AocError::Parse { line: 2, column: 3, .. }));
+    }
+
+    #[test]
+    fn parse_errors_quote_a_short_excerpt(){
+        let err = AocError::parse(1, 1, "R24\nR30\nL38", "expected a range like 11-22");
+        assert_eq!("line 1, column 1: expected a range like 11-22 (found \"R24...\")", err.to_string());
+    }
+
+    #[test]
+    fn stubs_say_their_part_is_not_written(){
+        assert_eq!("part 2 of day 7 is not written yet", AocError::Unwritten { day: 7, part: 2 }.to_string());
+    }
+}
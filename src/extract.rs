@@ -1,2 +1,7 @@
+pub mod answers;
 pub mod config;
+pub mod download;
 pub mod input;
+pub mod page;
+pub mod records;
+pub mod settings;
@@ -0,0 +1,117 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::extract::records;
+use crate::transform::solver::Part;
+
+/// Expected answers of one input, stored next to it as `<input>.answers`.
+///
+/// Each line holds a part number and its answer separated by a colon, e.g.
+/// `1: 1177`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn path_for(input: &Path) -> PathBuf {
+        let mut path = input.as_os_str().to_owned();
+        path.push(".answers");
+        PathBuf::from(path)
+    }
+
+    /// Reads the answers stored next to `input`. An input without an answers
+    /// file simply has no expected answers.
+    pub fn for_input(input: &Path) -> Result<Answers, AocError> {
+        records::load(&Answers::path_for(input), Answers::parse)
+    }
+
+    /// Stores these answers next to `input`, replacing any previous ones.
+    pub fn write(&self, input: &Path) -> Result<(), AocError> {
+        fs::write(Answers::path_for(input), self.to_string())?;
+        Ok(())
+    }
+
+    pub fn parse(content: &str) -> Result<Answers, AocError> {
+        let mut answers = Answers::default();
+
+        for line in records::lines(content) {
+            let (part, answer) = line.split_once(':')
+                .ok_or_else(|| AocError::parse_at(content, line, "expected `<part>: <answer>`"))?;
+            let part = part.trim().parse::<u8>().ok().and_then(Part::from_number)
+                .ok_or_else(|| AocError::parse_at(content, part, "expected part 1 or 2"))?;
+            answers.set(part, answer.trim());
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: &str) {
+        let slot = match part {
+            Part::One => &mut self.part_one,
+            Part::Two => &mut self.part_two,
+        };
+        *slot = Some(answer.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.part_one.is_none() && self.part_two.is_none()
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in [Part::One, Part::Two] {
+            if let Some(answer) = self.get(part) {
+                writeln!(f, "{}: {answer}", part.number())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers_per_part(){
+        let answers = Answers::parse("# day 1 example\n1: 3\n\n2:6\n").unwrap();
+        assert_eq!(Some("3"), answers.get(Part::One));
+        assert_eq!(Some("6"), answers.get(Part::Two));
+
+        let answers = Answers::parse("2: 6").unwrap();
+        assert_eq!(None, answers.get(Part::One));
+        assert!(!answers.is_empty());
+    }
+
+    #[test]
+    fn written_answers_parse_back(){
+        let mut answers = Answers::default();
+        answers.set(Part::Two, "6768");
+        assert_eq!("2: 6768\n", answers.to_string());
+        answers.set(Part::One, "1177");
+        assert_eq!(answers, Answers::parse(&answers.to_string()).unwrap());
+    }
+
+    #[test]
+    fn rejects_malformed_lines(){
+        assert!(matches!(Answers::parse("1: 3\n3: 4"), Err(AocError::Parse { line: 2, column: 1, .. })));
+        assert!(matches!(Answers::parse("1 3"), Err(AocError::Parse { line: 1, .. })));
+    }
+
+    #[test]
+    fn answers_live_next_to_their_input(){
+        assert_eq!(PathBuf::from("data/day/1/test.answers"), Answers::path_for(Path::new("data/day/1/test")));
+    }
+}
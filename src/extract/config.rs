@@ -1,17 +1,691 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::AocError;
+use crate::extract::settings::{self, Settings};
+use crate::http;
+use crate::load::output::Format;
+use crate::transform::solver::Part;
+use crate::transform::trace::Level;
+
+/// The year the puzzles are from unless `--year` says otherwise.
+pub const DEFAULT_YEAR: u16 = 2025;
+
+/// The first Advent of Code.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Advent of Code ran for 25 days until 2024 and runs for twelve since 2025.
+pub fn last_day(year: u16) -> u8 {
+    if year < 2025 { 25 } else { 12 }
+}
+
+pub const USAGE: &str = "\
+Usage: aoc-2025 <command> [options]
+
+Commands:
+  run     Solve a day with its puzzle input, or every day without --day
+  test    Solve a day with its examples and check their answers, or the
+          examples of every day without --day
+  verify  Check every recorded answer still comes out the same, or only
+          those of --day
+  profiles
+          Solve every profile input of a day, or of every day without --day,
+          and check each against its recorded answers
+  bench   Time the parse and solve steps of a day
+  submit  Solve a part of a day and send its answer to Advent of Code, using
+          the session token in AOC_SESSION
+  import  Save the examples of a saved puzzle page (--page) as the test
+          files of a day, with their answers
+  list    List the available days and parts
+  readme  Solve every day and rewrite the calendar table of README.md with
+          its stars, times and sources
+  new     Create the module and data directory for a new day
+  help    Print this message
+
+Options:
+  -y, --year <n>         Year of the puzzles (default: 2025)
+  -d, --day <n>          Day to work on (1-12, or 1-25 before 2025)
+  -p, --part <n>         Part to solve (1 or 2); both parts when omitted
+  -i, --input <path>     Read the puzzle input from <path>, or from stdin for -
+  -t, --text <text>      Use <text> as the puzzle input
+      --profile <name>   Use the input of someone else, saved as
+                         day/<n>/inputs/<name> in the data directory
+      --data-dir <dir>   Directory holding <year>/day/<n>/input, or day/<n>/input
+                         for 2025 (default: ./data, or the data directory of
+                         this repository)
+      --download         Download missing puzzle inputs into the data directory,
+                         using the session token in AOC_SESSION
+  -e, --example          Same as the test command; only with run or test
+  -r, --record           Store the answers of run next to the input, e.g. in
+                         data/day/<n>/input.answers, once they are accepted
+      --force            Let --record replace answers recorded differently
+  -w, --watch            Solve run or test again whenever the data files of
+                         --day change, until stopped with Ctrl-C
+  -f, --format <format>  Output format: text, plain, json or csv
+  -n, --runs <n>         Measured runs of bench (default: 10)
+      --warmup <n>       Unmeasured runs of bench before measuring (default: 2)
+      --page <path>      Saved puzzle description page to import examples from
+      --config <path>    Read the defaults from <path> instead of ./aoc.toml
+      --session-file <path>
+                         File holding the session token, read only by submit
+                         and --download
+      --request-timeout <seconds>
+                         Time to wait for Advent of Code to answer (default: 30)
+      --timeout <seconds>
+                         Report a part as TIMEOUT when it takes longer than
+                         <seconds> (default: no limit)
+      --trace[=<level>]  Write the steps the solvers take to stderr, at the
+                         step level or also with the detail of each step
+      --trace-file <path>
+                         Write the steps to <path> instead of stderr
+  -a, --answer <value>   Answer to submit instead of solving the part
+      --base-url <url>   Advent of Code server to talk to (default:
+                         https://adventofcode.com)
+  -h, --help             Print this message
+
+The day and part may also be given positionally, e.g. `aoc-2025 run 3 2`.
+`aoc-2025 <day> <part>` is a shorthand for `aoc-2025 run <day> <part>`.
+
+Defaults are read from aoc.toml, or the file in AOC_CONFIG, then from the
+environment, and options on the command line override both:
+  year, data_dir, format, base_url, session, session_file, request_timeout and
+  solver_timeout may be set in the file, and AOC_YEAR, AOC_DATA_DIR,
+  AOC_FORMAT, AOC_BASE_URL, AOC_SESSION, AOC_SESSION_FILE, AOC_REQUEST_TIMEOUT
+  and AOC_SOLVER_TIMEOUT in the environment. The session and the session file
+  count as one setting: whichever is given last in that order is used.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Test,
+    Verify,
+    Profiles,
+    Bench,
+    Submit,
+    Import,
+    List,
+    Readme,
+    New,
+    Help,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Command> {
+        match name {
+            "run" => Some(Command::Run),
+            "test" => Some(Command::Test),
+            "verify" => Some(Command::Verify),
+            "profiles" => Some(Command::Profiles),
+            "bench" => Some(Command::Bench),
+            "submit" => Some(Command::Submit),
+            "import" => Some(Command::Import),
+            "list" => Some(Command::List),
+            "readme" => Some(Command::Readme),
+            "new" => Some(Command::New),
+            "help" => Some(Command::Help),
+            _ => None,
+        }
+    }
+
+    fn requires_day(self) -> bool {
+        matches!(self, Command::Bench | Command::Submit | Command::Import | Command::New)
+    }
+}
+
+/// Where the puzzle input comes from when it is not the day's input file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+#[derive(Debug)]
 pub struct Config {
-    pub day: String,
-    pub iteration: String
+    pub command: Command,
+    pub year: u16,
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub input: Option<InputSource>,
+    /// Name of someone else's input to use instead of the day's own.
+    pub profile: Option<String>,
+    pub data_dir: PathBuf,
+    pub format: Format,
+    pub record: bool,
+    /// Whether `--record` may replace an answer that was recorded differently.
+    pub force: bool,
+    pub runs: u32,
+    pub warmup: u32,
+    /// Answer given on the command line to submit as is.
+    pub answer: Option<String>,
+    pub base_url: String,
+    /// Saved puzzle page to import examples from.
+    pub page: Option<PathBuf>,
+    /// Fetch day inputs that are not in the data directory yet.
+    pub download: bool,
+    /// Solve again whenever the data files of the day change.
+    pub watch: bool,
+    /// Session cookie of an Advent of Code login, for downloads and submissions.
+    pub session: Option<String>,
+    /// File the session cookie is read from when `session` is not given.
+    pub session_file: Option<PathBuf>,
+    pub request_timeout: Duration,
+    /// Time each part may take before it is reported as timed out.
+    pub solver_timeout: Option<Duration>,
+    /// How much the solvers explain of their work, when asked to.
+    pub trace: Option<Level>,
+    /// File the steps are written to instead of stderr.
+    pub trace_file: Option<PathBuf>,
 }
 
 impl Config {
-    pub fn new(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("not enough arguments");
+    /// Reads the settings file and the environment, then `args` on top of
+    /// them.
+    pub fn load(args: &[String]) -> Result<Config, AocError> {
+        let file = match settings_file(args) {
+            Some(path) => Settings::from_file(&path, true)?,
+            None => Settings::from_file(&default_settings_file(), false)?,
+        };
+        let settings = file.overridden_by(Settings::from_env(|name| env::var(name).ok())?);
+
+        Config::with_settings(args, settings)
+    }
+
+    /// The session token, given directly or else read from `session_file`.
+    /// Only the commands that talk to Advent of Code ask for it, so a missing
+    /// file does not stop the others.
+    pub fn session(&self) -> Result<Option<String>, AocError> {
+        match (&self.session, &self.session_file) {
+            (Some(session), _) => Ok(Some(session.clone())),
+            (None, Some(path)) => match fs::read_to_string(path) {
+                Ok(session) => Ok(Some(session.trim().to_string())),
+                Err(err) if err.kind() == ErrorKind::NotFound => Err(AocError::MissingSessionFile(path.clone())),
+                Err(err) => Err(AocError::Io(err)),
+            },
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Reads `args` alone, with the built-in defaults.
+    pub fn new(args: &[String]) -> Result<Config, AocError> {
+        Config::with_settings(args, Settings::default())
+    }
+
+    pub fn with_settings(args: &[String], settings: Settings) -> Result<Config, AocError> {
+        if args.len() < 2 {
+            return Err(AocError::BadArguments("not enough arguments".to_string()));
+        }
+
+        let mut args = args[1..].iter().map(String::as_str).peekable();
+
+        let command = match args.peek().copied().and_then(Command::from_name) {
+            Some(command) => {
+                args.next();
+                command
+            }
+            None => Command::Run,
+        };
+
+        let mut config = Config {
+            command,
+            year: settings.year.unwrap_or(DEFAULT_YEAR),
+            day: None,
+            part: None,
+            input: None,
+            profile: None,
+            data_dir: settings.data_dir.unwrap_or_else(default_data_dir),
+            format: settings.format.unwrap_or(Format::Text),
+            record: false,
+            force: false,
+            runs: 10,
+            warmup: 2,
+            answer: None,
+            base_url: settings.base_url.unwrap_or_else(|| http::DEFAULT_BASE_URL.to_string()),
+            page: None,
+            download: false,
+            watch: false,
+            session: settings.session,
+            session_file: settings.session_file,
+            request_timeout: settings.request_timeout.unwrap_or(http::DEFAULT_TIMEOUT),
+            solver_timeout: settings.solver_timeout,
+            trace: None,
+            trace_file: None,
+        };
+        let mut positional = vec![];
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value.or_else(|| args.next())
+                    .ok_or_else(|| AocError::BadArguments(format!("{flag} expects a value")))
+            };
+
+            match flag {
+                "-h" | "--help" => config.command = Command::Help,
+                "-y" | "--year" => config.year = parse_year(value()?)?,
+                "-d" | "--day" => config.day = Some(parse_day(value()?)?),
+                "-p" | "--part" => config.part = Some(parse_part(value()?)?),
+                "-i" | "--input" => config.input = Some(match value()? {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::File(PathBuf::from(path)),
+                }),
+                "-t" | "--text" => config.input = Some(InputSource::Text(value()?.to_string())),
+                "--profile" => config.profile = Some(parse_profile(value()?)?),
+                "--data-dir" => config.data_dir = PathBuf::from(value()?),
+                "--download" => config.download = true,
+                "-e" | "--example" if matches!(config.command, Command::Run | Command::Test) => config.command = Command::Test,
+                "-e" | "--example" => {
+                    return Err(AocError::BadArguments("--example can only be used with run or test".to_string()));
+                }
+                "-r" | "--record" => config.record = true,
+                "--force" => config.force = true,
+                "-w" | "--watch" => config.watch = true,
+                "-n" | "--runs" => config.runs = parse_count(flag, value()?)?.max(1),
+                "--warmup" => config.warmup = parse_count(flag, value()?)?,
+                "-f" | "--format" => config.format = parse_format(value()?)?,
+                "-a" | "--answer" => config.answer = Some(value()?.trim().to_string()),
+                "--base-url" => config.base_url = value()?.to_string(),
+                "--page" => config.page = Some(PathBuf::from(value()?)),
+                // Already read by `load` before anything else.
+                "--config" => { value()?; }
+                "--session-file" => {
+                    config.session_file = Some(PathBuf::from(value()?));
+                    config.session = None;
+                }
+                "--timeout" => {
+                    config.solver_timeout = Some(settings::parse_seconds(value()?)
+                        .map_err(|message| AocError::BadArguments(format!("{flag} {message}")))?);
+                }
+                "--trace" => config.trace = Some(match inline_value {
+                    Some(level) => parse_level(level)?,
+                    None => Level::Step,
+                }),
+                "--trace-file" => config.trace_file = Some(PathBuf::from(value()?)),
+                "--request-timeout" => {
+                    config.request_timeout = settings::parse_seconds(value()?)
+                        .map_err(|message| AocError::BadArguments(format!("{flag} {message}")))?;
+                }
+                _ if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(AocError::BadArguments(format!("unknown option {flag}")));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        if config.command == Command::Help {
+            return Ok(config);
+        }
+        if config.trace_file.is_some() && config.trace.is_none() {
+            config.trace = Some(Level::Step);
+        }
+
+        let mut positional = positional.into_iter();
+        if let Some(day) = positional.next() {
+            config.day = Some(parse_day(day)?);
+        }
+        if let Some(part) = positional.next() {
+            config.part = Some(parse_part(part)?);
+        }
+        if let Some(extra) = positional.next() {
+            return Err(AocError::BadArguments(format!("unexpected argument {extra:?}")));
+        }
+
+        if let Some(day) = config.day && day > last_day(config.year) {
+            return Err(AocError::BadArguments(format!("{} has no day {day}", config.year)));
+        }
+        if config.command.requires_day() && config.day.is_none() {
+            return Err(AocError::BadArguments("missing --day".to_string()));
+        }
+        if config.input.is_some() && config.day.is_none() {
+            return Err(AocError::BadArguments("--input and --text need --day".to_string()));
+        }
+        if config.record && config.command != Command::Run {
+            return Err(AocError::BadArguments("--record can only be used with run".to_string()));
+        }
+        if config.record && matches!(config.input, Some(InputSource::Text(_) | InputSource::Stdin)) {
+            return Err(AocError::BadArguments("--record needs the input to come from a file".to_string()));
+        }
+        if config.force && !config.record {
+            return Err(AocError::BadArguments("--force only applies to --record".to_string()));
+        }
+        if config.command == Command::Submit && config.part.is_none() {
+            return Err(AocError::BadArguments("submit needs --part".to_string()));
+        }
+        if config.command == Command::Import && config.page.is_none() {
+            return Err(AocError::BadArguments("import needs --page".to_string()));
+        }
+        if config.answer.is_some() && config.command != Command::Submit {
+            return Err(AocError::BadArguments("--answer can only be used with submit".to_string()));
+        }
+        if config.watch && (config.day.is_none() || !matches!(config.command, Command::Run | Command::Test)) {
+            return Err(AocError::BadArguments("--watch only works with run or test of one --day".to_string()));
+        }
+        if config.watch && (config.record || config.input == Some(InputSource::Stdin)) {
+            return Err(AocError::BadArguments("--watch cannot be used with --record or stdin".to_string()));
         }
+        if config.profile.is_some() && config.input.is_some() {
+            return Err(AocError::BadArguments("--profile cannot be used with --input or --text".to_string()));
+        }
+        if config.profile.is_some() && matches!(config.command, Command::Test | Command::Profiles) {
+            return Err(AocError::BadArguments("--profile cannot be used with the examples or every profile".to_string()));
+        }
+        if config.download && config.input.is_some() {
+            return Err(AocError::BadArguments("--download only fetches inputs into the data directory, not --input or --text".to_string()));
+        }
+        if config.download && config.profile.is_some() {
+            return Err(AocError::BadArguments("--download fetches your own input, not the one of --profile".to_string()));
+        }
+        if config.command == Command::Submit && config.profile.is_some() {
+            return Err(AocError::BadArguments("submit sends answers to your own account, not the one of --profile".to_string()));
+        }
+        if config.input.is_some() && config.command == Command::Test {
+            return Err(AocError::BadArguments("--input and --text cannot be used with the examples".to_string()));
+        }
+
+        Ok(config)
+    }
+}
+
+/// `./data` when running from the repository root, otherwise the data
+/// directory next to this crate's manifest, so the binary works from anywhere.
+fn default_data_dir() -> PathBuf {
+    let local = PathBuf::from("./data");
+    if local.is_dir() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+    }
+}
+
+pub(crate) fn parse_year(value: &str) -> Result<u16, AocError> {
+    value.parse::<u16>().ok()
+        .filter(|year| *year >= FIRST_YEAR)
+        .ok_or_else(|| AocError::BadArguments(format!("{value:?} is not a year of Advent of Code")))
+}
+
+/// The settings file given with `--config`, or else in `AOC_CONFIG`.
+fn settings_file(args: &[String]) -> Option<PathBuf> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    env::var_os("AOC_CONFIG").map(PathBuf::from)
+}
+
+/// `./aoc.toml`, or the one next to this crate's manifest, like the data
+/// directory.
+fn default_settings_file() -> PathBuf {
+    let local = PathBuf::from(settings::FILE_NAME);
+    if local.is_file() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(settings::FILE_NAME)
+    }
+}
+
+/// Days are only checked against the length of the year once every option
+/// has been read, since `--year` may come after the day.
+fn parse_day(value: &str) -> Result<u8, AocError> {
+    value.parse::<u8>().ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| AocError::BadArguments(format!("{value:?} is not a day between 1 and 25")))
+}
+
+fn parse_part(value: &str) -> Result<Part, AocError> {
+    value.parse::<u8>().ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| AocError::BadArguments(format!("{value:?} is not a part, expected 1 or 2")))
+}
+
+/// Profiles name files, so they cannot point outside the inputs directory.
+fn parse_profile(value: &str) -> Result<String, AocError> {
+    if value.is_empty() || value.starts_with('.') || value.contains(['/', '\\']) || value.ends_with(".answers") {
+        return Err(AocError::BadArguments(format!("{value:?} is not a profile name")));
+    }
+    Ok(value.to_string())
+}
+
+fn parse_level(value: &str) -> Result<Level, AocError> {
+    Level::from_name(value)
+        .ok_or_else(|| AocError::BadArguments(format!("{value:?} is not a trace level, expected step or detail")))
+}
+
+fn parse_count(flag: &str, value: &str) -> Result<u32, AocError> {
+    value.parse::<u32>()
+        .map_err(|_| AocError::BadArguments(format!("{flag} expects a number, found {value:?}")))
+}
+
+pub(crate) fn parse_format(value: &str) -> Result<Format, AocError> {
+    match value {
+        "text" => Ok(Format::Text),
+        "plain" => Ok(Format::Plain),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(AocError::BadArguments(format!("{value:?} is not a format, expected text, plain, json or csv"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        std::iter::once("aoc-2025").chain(line.split_whitespace()).map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_subcommand_with_flags(){
+        let config = Config::new(&args("run --day 3 --part=2 --input other.txt -f plain")).unwrap();
+        assert_eq!(Command::Run, config.command);
+        assert_eq!(Some(3), config.day);
+        assert_eq!(Some(Part::Two), config.part);
+        assert_eq!(Some(InputSource::File(PathBuf::from("other.txt"))), config.input);
+        assert_eq!(Format::Plain, config.format);
+    }
+
+    #[test]
+    fn positional_day_and_part_default_to_run(){
+        let config = Config::new(&args("1 2")).unwrap();
+        assert_eq!(Command::Run, config.command);
+        assert_eq!(Some(1), config.day);
+        assert_eq!(Some(Part::Two), config.part);
+
+        let config = Config::new(&args("new 7")).unwrap();
+        assert_eq!(Command::New, config.command);
+        assert_eq!(Some(7), config.day);
+        assert_eq!(None, config.part);
+    }
+
+    #[test]
+    fn parses_input_sources(){
+        let config = Config::new(&args("run 1 --input - --data-dir /tmp/aoc")).unwrap();
+        assert_eq!(Some(InputSource::Stdin), config.input);
+        assert_eq!(PathBuf::from("/tmp/aoc"), config.data_dir);
+
+        let config = Config::new(&args("run 1 --text=L68")).unwrap();
+        assert_eq!(Some(InputSource::Text("L68".to_string())), config.input);
+    }
+
+    #[test]
+    fn example_flag_switches_to_test(){
+        let config = Config::new(&args("run -d 1 --example")).unwrap();
+        assert_eq!(Command::Test, config.command);
+        assert_eq!(Command::Test, Config::new(&args("test 1 -e")).unwrap().command);
+        assert!(matches!(Config::new(&args("bench 1 --example")), Err(AocError::BadArguments(_))));
+
+        let config = Config::new(&args("test")).unwrap();
+        assert_eq!(None, config.day);
+    }
+
+    #[test]
+    fn record_only_applies_to_run(){
+        let config = Config::new(&args("run 1 2 --record")).unwrap();
+        assert!(config.record);
+        assert!(matches!(Config::new(&args("verify --record")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 1 --record --text L68")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 1 --record --input -")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 1 --force")), Err(AocError::BadArguments(_))));
+        assert!(Config::new(&args("run 1 --record --force")).unwrap().force);
+
+        let config = Config::new(&args("verify")).unwrap();
+        assert_eq!(Command::Verify, config.command);
+    }
+
+    #[test]
+    fn parses_bench_options(){
+        let config = Config::new(&args("bench 2 1 --runs 5 --warmup=0")).unwrap();
+        assert_eq!(Command::Bench, config.command);
+        assert_eq!(5, config.runs);
+        assert_eq!(0, config.warmup);
+        assert!(matches!(Config::new(&args("bench 2 -n many")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn help_wins_over_missing_arguments(){
+        let config = Config::new(&args("run --help")).unwrap();
+        assert_eq!(Command::Help, config.command);
+    }
+
+    #[test]
+    fn rejects_out_of_range_values(){
+        assert!(matches!(Config::new(&args("run --day 13")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run --day 0")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run --day 1 --part 3")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("bench --part 1")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run --input other.txt")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run --day")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run --days 1")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn list_and_run_do_not_need_a_day(){
+        let config = Config::new(&args("list")).unwrap();
+        assert_eq!(Command::List, config.command);
+
+        let config = Config::new(&args("run --part 2")).unwrap();
+        assert_eq!(Command::Run, config.command);
+        assert_eq!(None, config.day);
+    }
+
+    #[test]
+    fn submit_needs_a_day_and_part(){
+        let config = Config::new(&args("submit 4 2 --answer 8409 --base-url http://127.0.0.1:8080")).unwrap();
+        assert_eq!(Command::Submit, config.command);
+        assert_eq!(Some(Part::Two), config.part);
+        assert_eq!(Some("8409".to_string()), config.answer);
+        assert_eq!("http://127.0.0.1:8080", config.base_url);
+
+        assert!(matches!(Config::new(&args("submit 4")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("submit --part 1")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 4 2 --answer 8409")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn download_only_fills_the_data_directory(){
+        let config = Config::new(&args("run 7 --download")).unwrap();
+        assert!(config.download);
+        assert_eq!(None, config.session);
+
+        assert!(matches!(Config::new(&args("run 7 --download --input other.txt")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 7 --download --profile alice")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn import_needs_a_day_and_page(){
+        let config = Config::new(&args("import 4 --page day4.html")).unwrap();
+        assert_eq!(Command::Import, config.command);
+        assert_eq!(Some(PathBuf::from("day4.html")), config.page);
+
+        assert!(matches!(Config::new(&args("import 4")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("import --page day4.html")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn year_decides_the_last_day(){
+        let config = Config::new(&args("run 3")).unwrap();
+        assert_eq!(DEFAULT_YEAR, config.year);
+
+        let config = Config::new(&args("run 25 --year 2024")).unwrap();
+        assert_eq!(2024, config.year);
+        assert_eq!(Some(25), config.day);
+
+        assert!(matches!(Config::new(&args("run 25 --year 2025")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 1 --year 2014")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn profiles_are_plain_names(){
+        let config = Config::new(&args("run 2 --profile alice")).unwrap();
+        assert_eq!(Some("alice".to_string()), config.profile);
+
+        let config = Config::new(&args("profiles")).unwrap();
+        assert_eq!(Command::Profiles, config.command);
+
+        assert!(matches!(Config::new(&args("run 2 --profile ../input")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 2 --profile alice --text L5")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("test 2 --profile alice")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("submit 1 1 --profile alice")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn trace_takes_an_optional_level(){
+        assert_eq!(None, Config::new(&args("run 1")).unwrap().trace);
+        assert_eq!(Some(Level::Step), Config::new(&args("run 1 --trace")).unwrap().trace);
+        assert_eq!(Some(Level::Detail), Config::new(&args("run 1 --trace=detail")).unwrap().trace);
+
+        let config = Config::new(&args("run 1 --trace-file trace.log")).unwrap();
+        assert_eq!(Some(Level::Step), config.trace);
+        assert_eq!(Some(PathBuf::from("trace.log")), config.trace_file);
+
+        assert!(matches!(Config::new(&args("run 1 --trace=all")), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn command_line_overrides_settings(){
+        let settings = Settings::parse("year = 2024
+format = \"csv\"\nsession = \"abc\"\nrequest_timeout = 5").unwrap();
+        let config = Config::with_settings(&args("run 25 -f json"), settings).unwrap();
+        assert_eq!(2024, config.year);
+        assert_eq!(Format::Json, config.format);
+        assert_eq!(Some("abc".to_string()), config.session);
+        assert_eq!(Duration::from_secs(5), config.request_timeout);
+
+        let config = Config::new(&args("run --config other.toml --request-timeout 0.5 --timeout 3")).unwrap();
+        assert_eq!(Duration::from_millis(500), config.request_timeout);
+        assert_eq!(Some(Duration::from_secs(3)), config.solver_timeout);
+        assert_eq!(Some(PathBuf::from("other.toml")), settings_file(&args("run --config other.toml")));
+    }
+
+    #[test]
+    fn session_file_on_the_command_line_overrides_a_session_setting(){
+        let settings = Settings::parse("session = \"abc\"").unwrap();
+        let config = Config::with_settings(&args("submit 1 1 --session-file /nonexistent/session"), settings).unwrap();
+        assert_eq!(None, config.session);
+        assert!(matches!(config.session(), Err(AocError::MissingSessionFile(_))));
+
+        let config = Config::with_settings(&args("list"), Settings::parse("session_file = \"/nonexistent/session\"").unwrap()).unwrap();
+        assert_eq!(Command::List, config.command);
+    }
 
-        let day = args[1].clone();
-        let iteration = args[2].clone();
+    #[test]
+    fn watch_needs_one_day(){
+        let config = Config::new(&args("test 4 --watch")).unwrap();
+        assert!(config.watch);
 
-        Ok(Config { day, iteration })
+        assert!(matches!(Config::new(&args("run --watch")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("bench 4 -w")), Err(AocError::BadArguments(_))));
+        assert!(matches!(Config::new(&args("run 4 -w --input -")), Err(AocError::BadArguments(_))));
     }
 }
@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::AocError;
+use crate::http::Client;
+
+/// Fetches puzzle inputs from Advent of Code, keeping them in the data
+/// directory.
+pub struct Downloader {
+    client: Client,
+}
+
+impl Downloader {
+    pub fn new(client: Client) -> Downloader {
+        Downloader { client }
+    }
+
+    /// Downloads the input of `day` of `year` into `path` unless it is
+    /// already there. Returns whether a request was made.
+    pub fn fetch(&self, year: u16, day: u8, path: &Path) -> Result<bool, AocError> {
+        if path.exists() {
+            return Ok(false);
+        }
+
+        let text = self.client.get(year, day, "/input")?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        // Written aside and renamed so an interrupted download is never
+        // mistaken for a cached input.
+        let partial = path.with_extension("download");
+        fs::write(&partial, text)?;
+        fs::rename(&partial, path)?;
+
+        Ok(true)
+    }
+}
@@ -1,25 +1,219 @@
 use std::fs;
-use std::error::Error;
+use std::io::{self, ErrorKind, Read};
+use std::path::{Path, PathBuf};
 
-use crate::extract::config::Config;
+use crate::error::AocError;
+use crate::extract::answers::Answers;
+use crate::extract::config::{Config, InputSource, DEFAULT_YEAR};
+use crate::extract::download::Downloader;
+use crate::http::Client;
 
+#[derive(Clone)]
 pub struct PuzzleInput {
+    pub year: u16,
     pub day: u8,
-    pub iteration: u8,
+    /// Where the text came from, e.g. `input` or `test`, for reports.
+    pub name: String,
     pub text: String
 }
 
 impl PuzzleInput {
-    pub fn new(config: &Config) -> Result<PuzzleInput, Box<dyn Error>> {
-        let file_path = format!("./data/day/{}/input", config.day);
+    pub fn new(config: &Config) -> Result<PuzzleInput, AocError> {
+        let day = config.day
+            .ok_or_else(|| AocError::BadArguments("missing --day".to_string()))?;
 
-        let day = config.day.parse::<u8>()?;
+        PuzzleInput::for_day(config, day)
+    }
+
+    /// Reads the input of `day`, which may differ from `config.day` when
+    /// running several days in one go. With `config.download` a missing input
+    /// is fetched into the data directory first.
+    pub fn for_day(config: &Config, day: u8) -> Result<PuzzleInput, AocError> {
+        match &config.input {
+            None | Some(InputSource::File(_)) => {
+                let path = PuzzleInput::path_for_day(config, day).unwrap_or_default();
+                if config.download && !path.exists() {
+                    let session = config.session()?
+                        .ok_or_else(|| AocError::BadArguments("--download needs a session token in AOC_SESSION or a session file".to_string()))?;
+                    Downloader::new(Client::new(&config.base_url, &session, config.request_timeout)).fetch(config.year, day, &path)?;
+                }
+                PuzzleInput::read(config.year, day, &path)
+            }
+            Some(InputSource::Stdin) => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Ok(PuzzleInput { year: config.year, day, name: "stdin".to_string(), text })
+            }
+            Some(InputSource::Text(text)) => {
+                Ok(PuzzleInput { year: config.year, day, name: "inline".to_string(), text: text.clone() })
+            }
+        }
+    }
+
+    /// A short, stable hash of the text, to tell inputs apart in reports
+    /// without printing them (64-bit FNV-1a, as hex).
+    pub fn fingerprint(&self) -> String {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        let hash = self.text.bytes()
+            .fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME));
+        format!("{hash:016x}")
+    }
+
+    /// The file the input of `day` is read from, unless it comes from stdin
+    /// or the command line. With `config.profile` it is the input of that
+    /// profile instead of the day's own.
+    pub fn path_for_day(config: &Config, day: u8) -> Option<PathBuf> {
+        match (&config.input, &config.profile) {
+            (None, Some(profile)) => Some(PuzzleInput::profile_path(config, day, profile)),
+            (None, None) => Some(PuzzleInput::day_directory(config, day).join("input")),
+            (Some(InputSource::File(path)), _) => Some(path.clone()),
+            (Some(InputSource::Stdin | InputSource::Text(_)), _) => None,
+        }
+    }
+
+    /// The input of someone else, kept as `inputs/<profile>` in the data
+    /// directory of `day`.
+    pub fn profile_path(config: &Config, day: u8, profile: &str) -> PathBuf {
+        PuzzleInput::day_directory(config, day).join("inputs").join(profile)
+    }
+
+    /// Names of the profiles with an input for `day`, sorted.
+    pub fn profiles(config: &Config, day: u8) -> Result<Vec<String>, AocError> {
+        let directory = PuzzleInput::day_directory(config, day).join("inputs");
+
+        let mut profiles: Vec<String> = match fs::read_dir(&directory) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|path| path.is_file() && path.extension().is_none_or(|ext| ext != "answers"))
+                .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+                .collect(),
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+            Err(err) => return Err(AocError::Io(err)),
+        };
+        profiles.sort();
+
+        Ok(profiles)
+    }
+
+    /// Reads the input of `profile` for `day` with its recorded answers.
+    pub fn for_profile(config: &Config, day: u8, profile: &str) -> Result<(PuzzleInput, Answers), AocError> {
+        let path = PuzzleInput::profile_path(config, day, profile);
+        Ok((PuzzleInput::read(config.year, day, &path)?, Answers::for_input(&path)?))
+    }
+
+    /// Expected answers recorded for the input of `day`.
+    pub fn answers_for_day(config: &Config, day: u8) -> Result<Answers, AocError> {
+        match PuzzleInput::path_for_day(config, day) {
+            Some(path) => Answers::for_input(&path),
+            None => Ok(Answers::default()),
+        }
+    }
+
+    /// Reads every example of `day`, i.e. the files in its data directory whose
+    /// name starts with `test`, together with their expected answers.
+    pub fn examples(config: &Config, day: u8) -> Result<Vec<(PuzzleInput, Answers)>, AocError> {
+        let directory = PuzzleInput::day_directory(config, day);
+
+        let mut paths: Vec<PathBuf> = match fs::read_dir(&directory) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<_, _>>()?,
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+            Err(err) => return Err(AocError::Io(err)),
+        };
+        paths.retain(|path| PuzzleInput::is_example(path));
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(AocError::MissingInput(directory.join("test")));
+        }
+
+        paths.iter()
+            .map(|path| Ok((PuzzleInput::read(config.year, day, path)?, Answers::for_input(path)?)))
+            .collect()
+    }
+
+    /// `<data_dir>/<year>/day/<day>`. Data kept in `<data_dir>/day/<day>`,
+    /// from before other years were supported, is still used for 2025.
+    pub fn day_directory(config: &Config, day: u8) -> PathBuf {
+        let directory = config.data_dir.join(config.year.to_string()).join("day").join(day.to_string());
+        let legacy = config.data_dir.join("day");
+
+        if config.year == DEFAULT_YEAR && !directory.exists() && legacy.is_dir() {
+            legacy.join(day.to_string())
+        } else {
+            directory
+        }
+    }
+
+    fn is_example(path: &Path) -> bool {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        path.is_file() && name.starts_with("test") && path.extension().is_none_or(|ext| ext != "answers")
+    }
+
+    fn read(year: u16, day: u8, file_path: &Path) -> Result<PuzzleInput, AocError> {
+        let text = fs::read_to_string(file_path).map_err(|err| match err.kind() {
+            ErrorKind::NotFound => AocError::MissingInput(file_path.to_path_buf()),
+            _ => AocError::Io(err),
+        })?;
+
+        let name = file_path.file_name()
+            .map_or_else(|| file_path.display().to_string(), |name| name.to_string_lossy().into_owned());
+
+        Ok(PuzzleInput {year, day, name, text})
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(line: &str) -> Config {
+        let args: Vec<String> = std::iter::once("aoc-2025").chain(line.split_whitespace()).map(String::from).collect();
+        Config::new(&args).unwrap()
+    }
 
-        let iteration = config.iteration.parse::<u8>()?;
+    #[test]
+    fn inline_text_is_used_as_is(){
+        let input = PuzzleInput::new(&config("run 1 --text L68")).unwrap();
+        assert_eq!("L68", input.text);
+        assert_eq!("inline", input.name);
+    }
 
-        let text = fs::read_to_string(file_path)?;
+    #[test]
+    fn fingerprint_depends_only_on_text(){
+        let input = PuzzleInput { year: 2025, day: 1, name: "a".to_string(), text: String::new() };
+        assert_eq!("cbf29ce484222325", input.fingerprint());
 
-        Ok(PuzzleInput {day, iteration, text})
+        let input = PuzzleInput { year: 2025, day: 2, name: "b".to_string(), text: "a".to_string() };
+        assert_eq!("af63dc4c8601ec8c", input.fingerprint());
     }
 
+    #[test]
+    fn data_dir_changes_where_inputs_are_read(){
+        let result = PuzzleInput::new(&config("run 1 --data-dir /nonexistent"));
+        assert!(matches!(result, Err(AocError::MissingInput(path)) if path == Path::new("/nonexistent/2025/day/1/input")));
+    }
+
+    #[test]
+    fn profiles_live_in_the_inputs_directory(){
+        let config = config("run 1 --profile alice --data-dir /nonexistent");
+        assert_eq!(Some(PathBuf::from("/nonexistent/2025/day/1/inputs/alice")), PuzzleInput::path_for_day(&config, 1));
+        assert!(PuzzleInput::profiles(&config, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn years_have_their_own_directory(){
+        let data_dir = env!("CARGO_MANIFEST_DIR");
+        let config_2025 = config(&format!("run 1 --data-dir {data_dir}/data"));
+        assert_eq!(Path::new(data_dir).join("data/day/1"), PuzzleInput::day_directory(&config_2025, 1));
+
+        let config_2024 = config(&format!("run 1 --year 2024 --data-dir {data_dir}/data"));
+        assert_eq!(Path::new(data_dir).join("data/2024/day/1"), PuzzleInput::day_directory(&config_2024, 1));
+    }
 }
@@ -0,0 +1,219 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::error::AocError;
+use crate::extract::answers::Answers;
+use crate::extract::config::Config;
+use crate::extract::input::PuzzleInput;
+use crate::transform::solver::Part;
+
+/// An example found in a saved puzzle page, with the answers the page gives
+/// for it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub text: String,
+    pub answers: Answers,
+}
+
+/// Reads the examples out of a saved puzzle description page.
+///
+/// Each part of the puzzle is an `<article>`. The example of part one is the
+/// first `<pre><code>` block of its article, and the answer of a part is the
+/// last highlighted `<code><em>` of its article. Part two reuses that example
+/// unless its article introduces a new one with "For example", since most of
+/// its other blocks only illustrate the steps of the first example.
+pub fn examples(page: &str) -> Result<Vec<Example>, AocError> {
+    let mut articles = sections(page, "<article", "</article>");
+    if articles.is_empty() {
+        articles.push(page);
+    }
+
+    let mut examples: Vec<Example> = vec![];
+    for (article, part) in articles.into_iter().zip([Part::One, Part::Two]) {
+        let answer = sections(article, "<code><em>", "</em></code>").last().map(|answer| text_of(answer));
+        let example = match part {
+            Part::One => first_block(article),
+            Part::Two => new_block(article)
+                .filter(|text| examples.iter().all(|example| example.text != *text)),
+        };
+
+        match (example, examples.last_mut()) {
+            (Some(text), _) => {
+                let mut answers = Answers::default();
+                if let Some(answer) = answer {
+                    answers.set(part, answer.trim());
+                }
+                examples.push(Example { text, answers });
+            }
+            (None, Some(previous)) => {
+                if let Some(answer) = answer {
+                    previous.answers.set(part, answer.trim());
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    if examples.is_empty() {
+        return Err(AocError::BadArguments("the page has no <pre><code> example block".to_string()));
+    }
+    Ok(examples)
+}
+
+/// Stores `examples` in the data directory of `day` as `test`, `test-2`, ...
+/// with their answers next to them. An existing example is only replaced when
+/// it is empty, as left by the `new` command, so hand-made examples are never
+/// lost; its answers are merged with the ones of the page.
+pub fn save_examples(config: &Config, day: u8, examples: &[Example]) -> Result<Vec<PathBuf>, AocError> {
+    let directory = PuzzleInput::day_directory(config, day);
+    fs::create_dir_all(&directory)?;
+
+    let mut paths = vec![];
+    for (index, example) in examples.iter().enumerate() {
+        let path = match index {
+            0 => directory.join("test"),
+            _ => directory.join(format!("test-{}", index + 1)),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing.trim().is_empty() => fs::write(&path, &example.text)?,
+            Ok(existing) if existing != example.text => {
+                return Err(AocError::BadArguments(format!("{} already holds a different example", path.display())));
+            }
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => fs::write(&path, &example.text)?,
+            Err(err) => return Err(AocError::Io(err)),
+        }
+
+        let mut answers = Answers::for_input(&path)?;
+        for part in [Part::One, Part::Two] {
+            if let Some(answer) = example.answers.get(part) {
+                answers.set(part, answer);
+            }
+        }
+        answers.write(&path)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+fn first_block(article: &str) -> Option<String> {
+    sections(article, "<pre><code>", "</code></pre>").first().map(|block| text_of(block))
+}
+
+/// The first block of `article` that follows a paragraph saying "For example".
+fn new_block(article: &str) -> Option<String> {
+    let mut rest = article;
+    while let Some(start) = rest.find("<pre><code>") {
+        let before = &rest[..start];
+        let paragraph = before.rfind("<p>").map_or(before, |index| &before[index..]);
+        rest = &rest[start..];
+        if paragraph.contains("For example") {
+            return first_block(rest);
+        }
+        rest = &rest["<pre><code>".len()..];
+    }
+    None
+}
+
+/// The contents of every `open ... close` pair of `text`, in order. `open`
+/// may be the start of a tag with attributes, e.g. `<article`.
+fn sections<'a>(text: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+        let body = if open.ends_with('>') {
+            after
+        } else {
+            after.find('>').map_or("", |index| &after[index + 1..])
+        };
+        let Some(end) = body.find(close) else {
+            break;
+        };
+        found.push(&body[..end]);
+        rest = &body[end + close.len()..];
+    }
+
+    found
+}
+
+/// The text of an HTML fragment without its tags and entities.
+fn text_of(fragment: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in fragment.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "\
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Secret Entrance ---</h2>
+<p>For example, suppose the attached document contained the following rotations:</p>
+<pre><code>L68
+L30
+R48
+</code></pre>
+<p>Following these rotations would cause the dial to move as follows:</p>
+<pre><code>The dial starts by pointing at <em>50</em>.
+</code></pre>
+<p>Because the dial points at <code>0</code> a total of three times, the password in this example is <code><em>3</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1177</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<p>Following the same rotations as in the above example, the dial points at zero a few extra times:</p>
+<pre><code>- The dial is rotated <em>L68</em> to point at 82.
+</code></pre>
+<p>In this example, the new password would be <code><em>6</em></code>.</p>
+</article>
+</main>";
+
+    #[test]
+    fn finds_the_example_and_both_answers(){
+        let examples = examples(PAGE).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!("L68\nL30\nR48\n", examples[0].text);
+        assert_eq!(Some("3"), examples[0].answers.get(Part::One));
+        assert_eq!(Some("6"), examples[0].answers.get(Part::Two));
+    }
+
+    #[test]
+    fn part_two_may_bring_its_own_example(){
+        let page = "\
+<article><p>For example:</p><pre><code>1 &lt; 2
+</code></pre><p>The answer is <code><em>7</em></code>.</p></article>
+<article><p>For example, given these pairs:</p><pre><code>3 &amp; 4
+</code></pre><p>The answer is <code><em>12</em></code>.</p></article>";
+        let examples = examples(page).unwrap();
+        assert_eq!(2, examples.len());
+        assert_eq!("1 < 2\n", examples[0].text);
+        assert_eq!(None, examples[0].answers.get(Part::Two));
+        assert_eq!("3 & 4\n", examples[1].text);
+        assert_eq!(Some("12"), examples[1].answers.get(Part::Two));
+        assert_eq!(None, examples[1].answers.get(Part::One));
+    }
+
+    #[test]
+    fn pages_without_examples_are_rejected(){
+        assert!(matches!(examples("<html><p>Please log in.</p></html>"), Err(AocError::BadArguments(_))));
+    }
+}
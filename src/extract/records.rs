@@ -0,0 +1,44 @@
+//! The small line-based files kept in the data directory, such as the answers
+//! of an input and the submissions of a day. Blank lines and lines starting
+//! with `#` are ignored in all of them.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::error::AocError;
+
+/// Reads the file at `path` with `parse`. A file that does not exist yet
+/// holds no records, so it gives the default value.
+pub fn load<T: Default>(path: &Path, parse: impl FnOnce(&str) -> Result<T, AocError>) -> Result<T, AocError> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse(&content),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(AocError::Io(err)),
+    }
+}
+
+/// The trimmed lines of `content` that hold a record. They are slices of
+/// `content`, so errors can point at them with `AocError::parse_at`.
+pub fn lines(content: &str) -> impl Iterator<Item = &str> {
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_blank_lines_and_comments(){
+        let content = "# answers\n1: 1177\n\n  2: 6768  \n";
+        assert_eq!(vec!["1: 1177", "2: 6768"], lines(content).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn missing_files_hold_no_records(){
+        let records: Vec<String> = load(Path::new("/nonexistent/submissions"), |_| unreachable!()).unwrap();
+        assert!(records.is_empty());
+    }
+}
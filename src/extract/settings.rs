@@ -0,0 +1,223 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::AocError;
+use crate::extract::config::{parse_format, parse_year};
+use crate::load::output::Format;
+
+/// Name of the configuration file looked for in the working directory.
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Defaults of `Config` that can be changed before the command line is read,
+/// from `aoc.toml` or from environment variables. Unset values keep the
+/// built-in defaults.
+///
+/// The file holds `key = value` lines, where values are quoted strings or
+/// numbers, e.g.
+///
+/// ```toml
+/// year = 2025
+/// data_dir = "data"
+/// format = "text"
+/// session_file = "~/.config/aoc/session"
+/// base_url = "https://adventofcode.com"
+/// request_timeout = 30
+/// solver_timeout = 60
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Settings {
+    pub year: Option<u16>,
+    pub data_dir: Option<PathBuf>,
+    pub format: Option<Format>,
+    pub base_url: Option<String>,
+    pub session: Option<String>,
+    /// File holding the session token, read when no token is given directly.
+    pub session_file: Option<PathBuf>,
+    pub request_timeout: Option<Duration>,
+    /// Time each part may take before it is reported as timed out.
+    pub solver_timeout: Option<Duration>,
+}
+
+/// Environment variables and the settings they override.
+const VARIABLES: [(&str, &str); 8] = [
+    ("AOC_YEAR", "year"),
+    ("AOC_DATA_DIR", "data_dir"),
+    ("AOC_FORMAT", "format"),
+    ("AOC_BASE_URL", "base_url"),
+    ("AOC_SESSION", "session"),
+    ("AOC_SESSION_FILE", "session_file"),
+    ("AOC_REQUEST_TIMEOUT", "request_timeout"),
+    ("AOC_SOLVER_TIMEOUT", "solver_timeout"),
+];
+
+impl Settings {
+    /// Reads the settings file at `path`. Relative paths in it are relative
+    /// to the directory of the file. A missing file is only an error when
+    /// `required`, i.e. when it was asked for explicitly.
+    pub fn from_file(path: &Path, required: bool) -> Result<Settings, AocError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound && !required => return Ok(Settings::default()),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Err(AocError::MissingSettings(path.to_path_buf())),
+            Err(err) => return Err(AocError::Io(err)),
+        };
+
+        let mut settings = Settings::parse(&content)?;
+        let base = path.parent().unwrap_or(Path::new("."));
+        settings.data_dir = settings.data_dir.map(|dir| base.join(dir));
+        settings.session_file = settings.session_file.map(|file| base.join(file));
+        Ok(settings)
+    }
+
+    pub fn parse(content: &str) -> Result<Settings, AocError> {
+        let mut settings = Settings::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| AocError::parse_at(content, line, "expected `<key> = <value>`"))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => quoted.split_once('"')
+                    .filter(|(_, rest)| rest.trim().is_empty() || rest.trim().starts_with('#'))
+                    .map(|(text, _)| text)
+                    .ok_or_else(|| AocError::parse_at(content, value, "unterminated string"))?,
+                None => value.split('#').next().unwrap_or_default().trim(),
+            };
+
+            settings.set(key, value).map_err(|message| AocError::parse_at(content, key, message))?;
+        }
+
+        Ok(settings)
+    }
+
+    /// Reads the `AOC_*` variables through `var`, normally `std::env::var`.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Result<Settings, AocError> {
+        let mut settings = Settings::default();
+
+        for (name, key) in VARIABLES {
+            if let Some(value) = var(name).filter(|value| !value.is_empty()) {
+                settings.set(key, &value).map_err(|message| AocError::BadArguments(format!("{name}: {message}")))?;
+            }
+        }
+
+        Ok(settings)
+    }
+
+    /// These settings with the ones set in `other` replacing them.
+    /// The session token and its file count as one setting, so a file given
+    /// in `other` also replaces a token given here.
+    pub fn overridden_by(self, other: Settings) -> Settings {
+        let (session, session_file) = if other.session.is_some() || other.session_file.is_some() {
+            (other.session, other.session_file)
+        } else {
+            (self.session, self.session_file)
+        };
+        Settings {
+            year: other.year.or(self.year),
+            data_dir: other.data_dir.or(self.data_dir),
+            format: other.format.or(self.format),
+            base_url: other.base_url.or(self.base_url),
+            session,
+            session_file,
+            request_timeout: other.request_timeout.or(self.request_timeout),
+            solver_timeout: other.solver_timeout.or(self.solver_timeout),
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "year" => self.year = Some(parse_year(value).map_err(|err| err.to_string())?),
+            "data_dir" => self.data_dir = Some(PathBuf::from(value)),
+            "format" => self.format = Some(parse_format(value).map_err(|err| err.to_string())?),
+            "base_url" => self.base_url = Some(value.to_string()),
+            "session" => self.session = Some(value.trim().to_string()),
+            "session_file" => self.session_file = Some(expand_home(value)),
+            "request_timeout" => self.request_timeout = Some(parse_seconds(value)?),
+            "solver_timeout" => self.solver_timeout = Some(parse_seconds(value)?),
+            _ => return Err(format!("unknown setting {key:?}")),
+        }
+        Ok(())
+    }
+}
+
+/// Reads a timeout given in seconds.
+pub fn parse_seconds(value: &str) -> Result<Duration, String> {
+    value.parse::<f64>().ok()
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("expected a number of seconds, found {value:?}"))
+}
+
+/// Replaces a leading `~/` by the home directory, as shells do.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_settings_file(){
+        let settings = Settings::parse("# defaults\nyear = 2024\ndata_dir = \"puzzles\" # mine\nformat=\"json\"\nrequest_timeout = 2.5\nsolver_timeout = 10\n").unwrap();
+        assert_eq!(Some(2024), settings.year);
+        assert_eq!(Some(PathBuf::from("puzzles")), settings.data_dir);
+        assert_eq!(Some(Format::Json), settings.format);
+        assert_eq!(Some(Duration::from_millis(2500)), settings.request_timeout);
+        assert_eq!(Some(Duration::from_secs(10)), settings.solver_timeout);
+        assert_eq!(None, settings.session);
+    }
+
+    #[test]
+    fn rejects_unknown_or_malformed_settings(){
+        assert!(matches!(Settings::parse("year = 2025\ncolour = \"red\""), Err(AocError::Parse { line: 2, column: 1, .. })));
+        assert!(matches!(Settings::parse("format = \"yaml\""), Err(AocError::Parse { line: 1, .. })));
+        assert!(matches!(Settings::parse("data_dir = \"data"), Err(AocError::Parse { line: 1, column: 12, .. })));
+        assert!(matches!(Settings::parse("year"), Err(AocError::Parse { .. })));
+    }
+
+    #[test]
+    fn only_a_requested_settings_file_must_exist(){
+        let path = Path::new("/nonexistent/aoc.toml");
+        assert_eq!(Settings::default(), Settings::from_file(path, false).unwrap());
+        let err = Settings::from_file(path, true).unwrap_err();
+        assert_eq!("settings file /nonexistent/aoc.toml does not exist", err.to_string());
+    }
+
+    #[test]
+    fn environment_overrides_file(){
+        let file = Settings::parse("year = 2024\nformat = \"csv\"").unwrap();
+        let env = Settings::from_env(|name| match name {
+            "AOC_YEAR" => Some("2025".to_string()),
+            "AOC_SESSION" => Some("abc123\n".to_string()),
+            _ => None,
+        }).unwrap();
+
+        let settings = file.overridden_by(env);
+        assert_eq!(Some(2025), settings.year);
+        assert_eq!(Some(Format::Csv), settings.format);
+        assert_eq!(Some("abc123".to_string()), settings.session);
+
+        assert!(matches!(Settings::from_env(|_| Some("x".to_string())), Err(AocError::BadArguments(_))));
+    }
+
+    #[test]
+    fn a_session_file_overrides_a_session_given_before(){
+        let file = Settings::parse("session = \"abc123\"").unwrap();
+        let env = Settings::from_env(|name| (name == "AOC_SESSION_FILE").then(|| "/run/session".to_string())).unwrap();
+
+        let settings = file.overridden_by(env);
+        assert_eq!(None, settings.session);
+        assert_eq!(Some(PathBuf::from("/run/session")), settings.session_file);
+    }
+}
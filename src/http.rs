@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use ureq::{Agent, AgentBuilder};
+
+use crate::error::AocError;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Advent of Code asks automated tools to say who they are and where to find
+/// them.
+pub const USER_AGENT: &str = concat!(
+    "aoc-2025/", env!("CARGO_PKG_VERSION"), " (+https://github.com/DiegoAsterio/aoc-2025)"
+);
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn agent(timeout: Duration) -> Agent {
+    AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(timeout)
+        .build()
+}
+
+fn session_cookie(session: &str) -> String {
+    format!("session={}", session.trim())
+}
+
+/// `{base_url}/{year}/day/{day}` followed by `path`, without doubled slashes.
+fn day_url(base_url: &str, year: u16, day: u8, path: &str) -> String {
+    format!("{}/{year}/day/{day}{path}", base_url.trim_end_matches('/'))
+}
+
+/// Talks to Advent of Code on behalf of the owner of a session token.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    /// `base_url` is normally `DEFAULT_BASE_URL`; tests point it at a local
+    /// server instead.
+    pub fn new(base_url: &str, session: &str, timeout: Duration) -> Client {
+        Client {
+            agent: agent(timeout),
+            base_url: base_url.to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// The body of the page at `path` of `day` of `year`.
+    pub fn get(&self, year: u16, day: u8, path: &str) -> Result<String, AocError> {
+        Ok(self.agent
+            .get(&day_url(&self.base_url, year, day, path))
+            .set("Cookie", &session_cookie(&self.session))
+            .call()?
+            .into_string()?)
+    }
+
+    /// Sends `form` to `path` of `day` of `year` and gives the body of the
+    /// page that answers it.
+    pub fn post_form(&self, year: u16, day: u8, path: &str, form: &[(&str, &str)]) -> Result<String, AocError> {
+        Ok(self.agent
+            .post(&day_url(&self.base_url, year, day, path))
+            .set("Cookie", &session_cookie(&self.session))
+            .send_form(form)?
+            .into_string()?)
+    }
+}
@@ -1,3 +1,39 @@
+//! Solutions to Advent of Code, runnable from the `aoc-2025` command or
+//! called directly on puzzle inputs held in memory:
+//!
+//! ```
+//! use aoc_2025::{Answer, Part};
+//!
+//! let answer = aoc_2025::solve(1, Part::One, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+//! assert_eq!(Answer::Unsigned(3), answer);
+//! ```
+//!
+//! The structured input of each day, e.g. `transform::day::one::Movement`,
+//! is public too, and `transform::day::parse` parses an input once for
+//! solving both of its parts.
+
+pub mod error;
 pub mod extract;
 pub mod transform;
 pub mod load; 
+pub mod runner;
+pub mod bench;
+pub mod http;
+pub mod scaffold;
+pub mod watch;
+
+pub use error::AocError;
+pub use load::output::{Answer, PuzzleOutput};
+pub use transform::solver::Part;
+
+use extract::config::DEFAULT_YEAR;
+
+/// Solves `part` of `day` of 2025 for `input`, the text of a puzzle input.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer, AocError> {
+    solve_year(DEFAULT_YEAR, day, part, input)
+}
+
+/// Solves `part` of `day` of `year`, for days of other years.
+pub fn solve_year(year: u16, day: u8, part: Part, input: &str) -> Result<Answer, AocError> {
+    Ok(transform::day::solve(year, day, part, input)?.answer)
+}
@@ -1 +1,5 @@
 pub mod output;
+pub mod readme;
+pub mod report;
+pub mod submissions;
+pub mod submit;
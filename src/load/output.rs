@@ -1,9 +1,327 @@
+use std::fmt::{self, Write};
+
+use crate::runner::PartRun;
+use crate::transform::solver::Part;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Sentences and tables for humans reading the terminal.
+    Text,
+    /// Only the answers, one per line, to pipe into other tools.
+    Plain,
+    /// A JSON array with one object per part.
+    Json,
+    /// A header line followed by one line per part.
+    Csv,
+}
+
+/// The answer of a part, keeping the type the solver computed it with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    /// An integer that does not fit in 64 bits.
+    Big(i128),
+    Text(String),
+    /// Letters drawn on a grid, one string per row, read by a human.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Reads an answer typed by hand, e.g. with `--answer`, as the narrowest
+    /// integer that holds it, or as text.
+    pub fn parse(text: &str) -> Answer {
+        if let Ok(value) = text.parse() {
+            Answer::Unsigned(value)
+        } else if let Ok(value) = text.parse() {
+            Answer::Signed(value)
+        } else if let Ok(value) = text.parse() {
+            Answer::Big(value)
+        } else {
+            Answer::Text(text.to_string())
+        }
+    }
+
+    /// Name of the variant, for scripts reading serialized answers.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Signed(_) => "signed",
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Big(_) => "big",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+        }
+    }
+
+    /// The answer on a single line: grids are summarized by their size.
+    pub fn inline(&self) -> String {
+        match self {
+            Answer::Grid(rows) => {
+                let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or_default();
+                format!("{}x{width} grid", rows.len())
+            }
+            answer => answer.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Big(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        Answer::Signed(value)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        Answer::Signed(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Answer {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Answer {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+/// What a part produced: its answer and, optionally, named values computed on
+/// the way, e.g. how many ranges were left after merging them.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PuzzleOutput {
-    pub result: String
+    pub answer: Answer,
+    pub diagnostics: Vec<(&'static str, String)>,
 }
 
 impl PuzzleOutput {
+    pub fn new(answer: impl Into<Answer>) -> PuzzleOutput {
+        PuzzleOutput { answer: answer.into(), diagnostics: vec![] }
+    }
+
+    pub fn with_diagnostic(mut self, name: &'static str, value: impl fmt::Display) -> PuzzleOutput {
+        self.diagnostics.push((name, value.to_string()));
+        self
+    }
+
     pub fn show(&self) {
-        println!("The code for the elves is: {}", self.result)
+        self.show_as("The code for the elves is");
+    }
+
+    /// Shows the answer of `part` next to the answers of the other parts.
+    pub fn show_part(&self, part: Part) {
+        self.show_as(&format!("Part {}", part.number()));
+    }
+
+    fn show_as(&self, label: &str) {
+        match &self.answer {
+            Answer::Grid(_) => println!("{label}:\n{}", self.answer),
+            answer => println!("{label}: {answer}"),
+        }
+        for (name, value) in &self.diagnostics {
+            println!("  {name}: {value}");
+        }
+    }
+}
+
+/// Renders `runs` for scripts. Failed parts have no answer: `-` in plain
+/// output, `null` in JSON and an empty field in CSV. JSON also carries the
+/// kind of each answer and the diagnostics of its part.
+pub fn render(runs: &[PartRun], format: Format) -> String {
+    match format {
+        Format::Text | Format::Plain => runs.iter()
+            .map(|run| run.outcome.as_ref().map_or("-".to_string(), |output| output.answer.to_string()))
+            .map(|answer| format!("{answer}\n"))
+            .collect(),
+        Format::Json => render_json(runs),
+        Format::Csv => render_csv(runs),
+    }
+}
+
+fn render_json(runs: &[PartRun]) -> String {
+    let mut json = String::from("[");
+
+    for (i, run) in runs.iter().enumerate() {
+        let output = run.outcome.as_ref().ok();
+        let answer = output.map(|output| output.answer.to_string());
+        let kind = output.map(|output| output.answer.kind());
+        let diagnostics = output.map_or_else(|| "{}".to_string(), |output| json_object(&output.diagnostics));
+        let error = run.outcome.as_ref().err().map(|err| err.to_string());
+
+        json.push_str(if i == 0 { "\n" } else { ",\n" });
+        let _ = write!(
+            json,
+            "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"kind\": {}, \"expected\": {}, \"status\": {}, \"error\": {}, \"parse_ms\": {}, \"elapsed_ms\": {:.3}, \"fingerprint\": {}, \"diagnostics\": {}}}",
+            run.year,
+            run.day,
+            run.part.number(),
+            json_string(Some(&run.input)),
+            json_string(answer.as_deref()),
+            json_string(kind),
+            json_string(run.expected.as_deref()),
+            json_string(Some(&run.status().to_string())),
+            json_string(error.as_deref()),
+            run.parse.map_or("null".to_string(), milliseconds),
+            run.elapsed.as_secs_f64() * 1000.0,
+            json_string(run.fingerprint.as_deref()),
+            diagnostics,
+        );
+    }
+
+    json.push_str(if runs.is_empty() { "]\n" } else { "\n]\n" });
+    json
+}
+
+fn milliseconds(duration: std::time::Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields.iter()
+        .map(|(name, value)| format!("{}: {}", json_string(Some(name)), json_string(Some(value))))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn json_string(value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return "null".to_string();
+    };
+
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => { let _ = write!(json, "\\u{:04x}", c as u32); }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn render_csv(runs: &[PartRun]) -> String {
+    let mut csv = String::from("year,day,part,input,answer,kind,expected,status,parse_ms,elapsed_ms,fingerprint\n");
+
+    for run in runs {
+        let output = run.outcome.as_ref().ok();
+        let answer = output.map(|output| output.answer.to_string()).unwrap_or_default();
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{:.3},{}",
+            run.year,
+            run.day,
+            run.part.number(),
+            csv_field(&run.input),
+            csv_field(&answer),
+            output.map_or("", |output| output.answer.kind()),
+            csv_field(run.expected.as_deref().unwrap_or_default()),
+            run.status(),
+            run.parse.map_or_else(String::new, milliseconds),
+            run.elapsed.as_secs_f64() * 1000.0,
+            run.fingerprint.as_deref().unwrap_or_default(),
+        );
+    }
+
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::error::AocError;
+
+    fn runs() -> Vec<PartRun> {
+        vec![
+            PartRun {
+                year: 2025,
+                day: 1,
+                part: Part::One,
+                input: "input".to_string(),
+                elapsed: Duration::from_micros(1500),
+                parse: Some(Duration::from_micros(250)),
+                outcome: Ok(PuzzleOutput::new(1177u64).with_diagnostic("crossings", 6)),
+                expected: Some("1177".to_string()),
+                fingerprint: Some("cbf29ce484222325".to_string()),
+            },
+            PartRun::failed(2025, 2, Part::Two, "in\"put", AocError::UnknownDay { year: 2025, day: 2 }),
+        ]
+    }
+
+    #[test]
+    fn renders_plain_answers(){
+        assert_eq!("1177\n-\n", render(&runs(), Format::Plain));
+    }
+
+    #[test]
+    fn renders_json(){
+        let json = render(&runs(), Format::Json);
+        assert!(json.starts_with("[\n  {\"year\": 2025, \"day\": 1, \"part\": 1, \"input\": \"input\", \"answer\": \"1177\", \"kind\": \"unsigned\", \"expected\": \"1177\", \"status\": \"PASS\", \"error\": null, \"parse_ms\": 0.250, \"elapsed_ms\": 1.500, \"fingerprint\": \"cbf29ce484222325\", \"diagnostics\": {\"crossings\": \"6\"}},\n"));
+        assert!(json.contains("\"input\": \"in\\\"put\", \"answer\": null, \"kind\": null"));
+        assert!(json.ends_with("\"diagnostics\": {}}\n]\n"));
+        assert_eq!("[]\n", render(&[], Format::Json));
+    }
+
+    #[test]
+    fn renders_csv(){
+        let csv = render(&runs(), Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!("year,day,part,input,answer,kind,expected,status,parse_ms,elapsed_ms,fingerprint", lines[0]);
+        assert_eq!("2025,1,1,input,1177,unsigned,1177,PASS,0.250,1.500,cbf29ce484222325", lines[1]);
+        assert_eq!("2025,2,2,\"in\"\"put\",,,,FAIL,,0.000,", lines[2]);
+    }
+
+    #[test]
+    fn answers_keep_their_type(){
+        assert_eq!(Answer::Unsigned(1177), Answer::parse("1177"));
+        assert_eq!(Answer::Signed(-3), Answer::parse("-3"));
+        assert_eq!(Answer::Big(1 << 70), Answer::parse(&(1i128 << 70).to_string()));
+        assert_eq!(Answer::Text("EHZRUCRB".to_string()), Answer::parse("EHZRUCRB"));
+
+        let grid = Answer::Grid(vec!["#..#".to_string(), "####".to_string()]);
+        assert_eq!("#..#\n####", grid.to_string());
+        assert_eq!("2x4 grid", grid.inline());
+        assert_eq!("grid", grid.kind());
     }
 }
@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use crate::extract::config::last_day;
+use crate::load::report::format_duration;
+use crate::runner::{PartRun, Status};
+use crate::scaffold::module_name;
+use crate::transform::{day, solver::Part};
+
+/// Lines around the calendar in the README. Everything between them is
+/// replaced; everything else is left as written.
+pub const START_MARKER: &str = "<!-- calendar start -->";
+pub const END_MARKER: &str = "<!-- calendar end -->";
+
+/// A markdown table with one row per day of `year`: its title, a star for
+/// every part whose answer matches the recorded one, the time to parse its
+/// input, the time to solve each part and a link to the source of its solver.
+pub fn calendar(year: u16, runs: &[PartRun]) -> String {
+    let mut table = String::from("| Day | Title | Stars | Parse | Part 1 | Part 2 | Source |\n|----:|-------|:-----:|------:|-------:|-------:|--------|\n");
+
+    for number in 1..=last_day(year) {
+        let Some(puzzle) = day::find(year, number) else {
+            let _ = writeln!(table, "| {number} | | | | | | |");
+            continue;
+        };
+        let day_runs: Vec<&PartRun> = runs.iter().filter(|run| run.year == year && run.day == number).collect();
+        let stars = day_runs.iter().filter(|run| run.status() == Status::Correct).count();
+        let time = |part: Part| match day_runs.iter().find(|run| run.part == part) {
+            Some(run) if run.outcome.is_ok() => format_duration(run.elapsed),
+            Some(run) => run.status().to_string(),
+            None => String::new(),
+        };
+        let parse = day_runs.iter().find_map(|run| run.parse).map_or_else(String::new, format_duration);
+        let source = module_name(year, number).map_or_else(String::new, |name| format!("[{name}.rs](src/transform/day/{name}.rs)"));
+
+        let _ = writeln!(
+            table,
+            "| {number} | {} | {} | {parse} | {} | {} | {source} |",
+            puzzle.title().replace('|', "\\|"), "⭐".repeat(stars), time(Part::One), time(Part::Two)
+        );
+    }
+
+    table
+}
+
+/// `readme` with the text between the calendar markers replaced by `table`.
+/// Without markers, a calendar section is added at the end.
+pub fn replace_calendar(readme: &str, table: &str) -> String {
+    let section = format!("{START_MARKER}\n{table}{END_MARKER}");
+
+    match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{section}{}", &readme[..start], &readme[end + END_MARKER.len()..])
+        }
+        _ => format!("{}\n\n## Calendar\n\n{section}\n", readme.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::error::AocError;
+    use crate::load::output::PuzzleOutput;
+
+    fn solved(day: u8, part: Part, answer: u64, expected: &str) -> PartRun {
+        PartRun {
+            year: 2025,
+            day,
+            part,
+            input: "input".to_string(),
+            elapsed: Duration::from_micros(1500),
+            parse: (part == Part::One).then_some(Duration::from_micros(250)),
+            outcome: Ok(PuzzleOutput::new(answer)),
+            expected: Some(expected.to_string()),
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn calendar_lists_every_day_of_the_year(){
+        let runs = vec![
+            solved(1, Part::One, 1177, "1177"),
+            solved(1, Part::Two, 6768, "6769"),
+            PartRun::failed(2025, 2, Part::One, "input", AocError::Overflow("adding up invalid ids")),
+        ];
+        let table = calendar(2025, &runs);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(2 + 12, lines.len());
+        assert_eq!("| 1 | Secret Entrance | ⭐ | 0.250 ms | 1.500 ms | 1.500 ms | [one.rs](src/transform/day/one.rs) |", lines[2]);
+        assert_eq!("| 2 | Gift Shop |  |  | FAIL |  | [two.rs](src/transform/day/two.rs) |", lines[3]);
+        assert_eq!("| 12 | | | | | | |", lines[13]);
+    }
+
+    #[test]
+    fn replaces_only_the_calendar_section(){
+        let readme = format!("Intro\n\n{START_MARKER}\nold\n{END_MARKER}\n\nOutro\n");
+        assert_eq!(format!("Intro\n\n{START_MARKER}\nnew\n{END_MARKER}\n\nOutro\n"), replace_calendar(&readme, "new\n"));
+
+        let readme = replace_calendar("Intro \n", "new\n");
+        assert_eq!(format!("Intro\n\n## Calendar\n\n{START_MARKER}\nnew\n{END_MARKER}\n"), readme);
+        assert_eq!(readme, replace_calendar(&readme, "new\n"));
+    }
+}
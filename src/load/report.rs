@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::bench::{Benchmark, Stats};
+use crate::runner::PartRun;
+use crate::transform::day;
+
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    if millis < 1000.0 {
+        format!("{millis:.3} ms")
+    } else {
+        format!("{:.3} s", millis / 1000.0)
+    }
+}
+
+/// Prints one row per part with its answer, status and the times to parse
+/// and solve it, followed by the details of every part that failed or gave a
+/// wrong answer.
+pub fn show_summary(runs: &[PartRun]) {
+    println!(
+        "{:>3} {:>4}  {:<22} {:<8} {:>20}  {:<6} {:>12} {:>12}",
+        "Day", "Part", "Title", "Input", "Answer", "Status", "Parse", "Time"
+    );
+
+    for run in runs {
+        let title = day::find(run.year, run.day).map(|puzzle| puzzle.title()).unwrap_or_default();
+        let answer = run.outcome.as_ref().map_or("-".to_string(), |output| output.answer.inline());
+        let parse = run.parse.map_or("-".to_string(), format_duration);
+        println!(
+            "{:>3} {:>4}  {:<22} {:<8} {:>20}  {:<6} {:>12} {:>12}",
+            run.day, run.part.number(), title, run.input, answer, run.status(), parse, format_duration(run.elapsed)
+        );
+    }
+
+    let solved = runs.iter().filter(|run| run.is_ok()).count();
+    let total: Duration = runs.iter().map(|run| run.elapsed + run.parse.unwrap_or_default()).sum();
+    println!("\n{solved} of {} parts solved in {}", runs.len(), format_duration(total));
+
+    let failures: Vec<&PartRun> = runs.iter().filter(|run| !run.is_ok()).collect();
+    if !failures.is_empty() {
+        println!("\nFailures:");
+        for run in failures {
+            let reason = match (&run.outcome, &run.expected) {
+                (Err(err), _) => err.to_string(),
+                (Ok(output), Some(expected)) => format!("expected {expected}, found {}", output.answer.inline()),
+                (Ok(_), None) => continue,
+            };
+            println!("  day {} part {} ({}): {reason}", run.day, run.part.number(), run.input);
+        }
+    }
+}
+
+pub fn show_benchmark(benchmark: &Benchmark) {
+    let title = day::find(benchmark.year, benchmark.day).map(|puzzle| puzzle.title()).unwrap_or_default();
+    println!(
+        "Day {} part {}: {title} ({} runs after {} warm-up runs)",
+        benchmark.day, benchmark.part.number(), benchmark.runs, benchmark.warmup
+    );
+    println!("  {:<6} {:>12} {:>12} {:>12} {:>12}", "", "min", "median", "mean", "stddev");
+
+    let rows: [(&str, &Stats); 3] = [("parse", &benchmark.parse), ("solve", &benchmark.solve), ("total", &benchmark.total)];
+    for (step, stats) in rows {
+        println!(
+            "  {step:<6} {:>12} {:>12} {:>12} {:>12}",
+            format_duration(stats.min), format_duration(stats.median), format_duration(stats.mean), format_duration(stats.stddev)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_switch_to_seconds(){
+        assert_eq!("1.500 ms", format_duration(Duration::from_micros(1500)));
+        assert_eq!("2.250 s", format_duration(Duration::from_millis(2250)));
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::error::AocError;
+use crate::extract::config::Config;
+use crate::extract::input::PuzzleInput;
+use crate::extract::records;
+use crate::load::submit::Verdict;
+use crate::transform::solver::Part;
+
+/// Every answer sent for one day with the verdict it got, stored in the data
+/// directory of the day as `submissions`.
+///
+/// Each line holds a part number, a verdict and the answer, e.g.
+/// `1 too-high 5123`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    entries: Vec<(Part, Verdict, String)>,
+}
+
+impl SubmissionLog {
+    pub fn path_for_day(config: &Config, day: u8) -> PathBuf {
+        PuzzleInput::day_directory(config, day).join("submissions")
+    }
+
+    /// Reads the log of `day`. A day without a log has no submissions yet.
+    pub fn for_day(config: &Config, day: u8) -> Result<SubmissionLog, AocError> {
+        records::load(&SubmissionLog::path_for_day(config, day), SubmissionLog::parse)
+    }
+
+    pub fn write(&self, config: &Config, day: u8) -> Result<(), AocError> {
+        let path = SubmissionLog::path_for_day(config, day);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn parse(content: &str) -> Result<SubmissionLog, AocError> {
+        let mut log = SubmissionLog::default();
+
+        for line in records::lines(content) {
+            let mut fields = line.splitn(3, ' ');
+            let (Some(part), Some(verdict), Some(answer)) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(AocError::parse_at(content, line, "expected `<part> <verdict> <answer>`"));
+            };
+            let part = part.parse::<u8>().ok().and_then(Part::from_number)
+                .ok_or_else(|| AocError::parse_at(content, part, "expected part 1 or 2"))?;
+            let verdict = verdict_from_name(verdict)
+                .ok_or_else(|| AocError::parse_at(content, verdict, "unknown verdict"))?;
+            log.record(part, answer.trim(), verdict);
+        }
+
+        Ok(log)
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) {
+        self.entries.push((part, verdict, answer.to_string()));
+    }
+
+    /// Checks `answer` against what earlier submissions of `part` already
+    /// tell about it, so answers that cannot be right are never sent.
+    pub fn check(&self, part: Part, answer: &str) -> Result<(), AocError> {
+        let entries = self.entries.iter().filter(|(entry_part, _, _)| *entry_part == part);
+        let candidate = answer.parse::<i128>().ok();
+        let mut too_high: Option<i128> = None;
+        let mut too_low: Option<i128> = None;
+
+        for (_, verdict, previous) in entries {
+            match verdict {
+                Verdict::Correct if previous == answer => {
+                    return Err(AocError::Rejected(format!("{answer} was already accepted")));
+                }
+                Verdict::Correct => {
+                    return Err(AocError::Rejected(format!("this part was already solved with {previous}")));
+                }
+                Verdict::AlreadySolved => {
+                    return Err(AocError::Rejected("this part was already solved".to_string()));
+                }
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if previous == answer => {
+                    return Err(AocError::Rejected(format!("{answer} was already marked {}", verdict_name(verdict).replace('-', " "))));
+                }
+                Verdict::TooHigh => {
+                    if let Ok(bound) = previous.parse::<i128>() {
+                        too_high = Some(too_high.map_or(bound, |high| high.min(bound)));
+                    }
+                }
+                Verdict::TooLow => {
+                    if let Ok(bound) = previous.parse::<i128>() {
+                        too_low = Some(too_low.map_or(bound, |low| low.max(bound)));
+                    }
+                }
+                Verdict::Wrong | Verdict::RateLimited(_) => {}
+            }
+        }
+
+        if let (Some(candidate), Some(high)) = (candidate, too_high) && candidate >= high {
+            return Err(AocError::Rejected(format!("{answer} is not below {high}, which was too high")));
+        }
+        if let (Some(candidate), Some(low)) = (candidate, too_low) && candidate <= low {
+            return Err(AocError::Rejected(format!("{answer} is not above {low}, which was too low")));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SubmissionLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (part, verdict, answer) in &self.entries {
+            writeln!(f, "{} {} {answer}", part.number(), verdict_name(verdict))?;
+        }
+        Ok(())
+    }
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::TooHigh => "too-high",
+        Verdict::TooLow => "too-low",
+        Verdict::Wrong => "wrong",
+        Verdict::RateLimited(_) => "rate-limited",
+        Verdict::AlreadySolved => "already-solved",
+    }
+}
+
+fn verdict_from_name(name: &str) -> Option<Verdict> {
+    match name {
+        "correct" => Some(Verdict::Correct),
+        "too-high" => Some(Verdict::TooHigh),
+        "too-low" => Some(Verdict::TooLow),
+        "wrong" => Some(Verdict::Wrong),
+        "rate-limited" => Some(Verdict::RateLimited(Duration::ZERO)),
+        "already-solved" => Some(Verdict::AlreadySolved),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_answers_already_marked_wrong(){
+        let log = SubmissionLog::parse("1 wrong abc\n1 too-high 500\n").unwrap();
+        assert!(matches!(log.check(Part::One, "abc"), Err(AocError::Rejected(_))));
+        assert!(matches!(log.check(Part::One, "500"), Err(AocError::Rejected(_))));
+        assert!(log.check(Part::One, "abd").is_ok());
+        assert!(log.check(Part::Two, "abc").is_ok());
+    }
+
+    #[test]
+    fn refuses_answers_outside_known_bounds(){
+        let log = SubmissionLog::parse("2 too-high 500\n2 too-low 100\n2 too-high 400\n2 rate-limited 50\n").unwrap();
+        assert!(log.check(Part::Two, "399").is_ok());
+        assert!(log.check(Part::Two, "101").is_ok());
+        assert!(matches!(log.check(Part::Two, "450"), Err(AocError::Rejected(_))));
+        assert!(matches!(log.check(Part::Two, "50"), Err(AocError::Rejected(_))));
+        assert!(log.check(Part::One, "450").is_ok());
+    }
+
+    #[test]
+    fn refuses_parts_already_solved(){
+        let log = SubmissionLog::parse("1 correct 1177").unwrap();
+        assert!(matches!(log.check(Part::One, "1177"), Err(AocError::Rejected(_))));
+        assert!(matches!(log.check(Part::One, "1178"), Err(AocError::Rejected(_))));
+    }
+
+    #[test]
+    fn written_log_parses_back(){
+        let mut log = SubmissionLog::default();
+        log.record(Part::One, "5123", Verdict::TooHigh);
+        log.record(Part::Two, "hello world", Verdict::Wrong);
+        assert_eq!("1 too-high 5123\n2 wrong hello world\n", log.to_string());
+        assert_eq!(log, SubmissionLog::parse(&log.to_string()).unwrap());
+
+        assert!(matches!(SubmissionLog::parse("1 maybe 3"), Err(AocError::Parse { line: 1, column: 3, .. })));
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::AocError;
+use crate::http::Client;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Part;
+
+/// What Advent of Code thought of a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was sent too recently; the next one is accepted after the wait.
+    RateLimited(Duration),
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "That's not the right answer; it is too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer; it is too low."),
+            Verdict::Wrong => write!(f, "That's not the right answer."),
+            Verdict::RateLimited(wait) => {
+                write!(f, "An answer was sent too recently; wait {}s before trying again.", wait.as_secs())
+            }
+            Verdict::AlreadySolved => write!(f, "This part is already solved."),
+        }
+    }
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page Advent of Code answers a submission
+    /// with. Returns `None` when the page does not look like a verdict.
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited(parse_wait(page).unwrap_or_default()))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Some(Verdict::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Reads the wait out of "You have 4m 51s left to wait".
+fn parse_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in page[start..end].split_whitespace() {
+        let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let number = number.parse::<u64>().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Sends answers to Advent of Code and reads back its verdicts.
+pub struct Submitter {
+    client: Client,
+}
+
+impl Submitter {
+    pub fn new(client: Client) -> Submitter {
+        Submitter { client }
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: Part, output: &PuzzleOutput) -> Result<Verdict, AocError> {
+        let level = part.number().to_string();
+        let answer = output.answer.to_string();
+        let page = self.client.post_form(year, day, "/answer", &[("level", &level), ("answer", &answer)])?;
+
+        Verdict::from_page(&page)
+            .ok_or_else(|| AocError::Http("the answer page did not contain a verdict".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_verdicts_from_pages(){
+        assert_eq!(Some(Verdict::Correct), Verdict::from_page("<p>That's the right answer!  You are one gold star closer.</p>"));
+        assert_eq!(Some(Verdict::TooHigh), Verdict::from_page("<p>That's not the right answer; your answer is too high.</p>"));
+        assert_eq!(Some(Verdict::TooLow), Verdict::from_page("<p>That's not the right answer; your answer is too low.</p>"));
+        assert_eq!(Some(Verdict::Wrong), Verdict::from_page("<p>That's not the right answer.  If you're stuck...</p>"));
+        assert_eq!(Some(Verdict::AlreadySolved), Verdict::from_page("<p>You don't seem to be solving the right level.</p>"));
+        assert_eq!(None, Verdict::from_page("<html>Log in</html>"));
+    }
+
+    #[test]
+    fn reads_wait_of_rate_limited_answers(){
+        let page = "<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 51s left to wait.</p>";
+        assert_eq!(Some(Verdict::RateLimited(Duration::from_secs(291))), Verdict::from_page(page));
+
+        let page = "<p>You gave an answer too recently.  You have 30s left to wait.</p>";
+        assert_eq!(Some(Verdict::RateLimited(Duration::from_secs(30))), Verdict::from_page(page));
+    }
+}
@@ -1,27 +1,322 @@
-use std::{env, process};
+use std::{env, fs, path::Path, process, sync::Arc};
 
-use aoc_2025::{extract::{config::Config, input::PuzzleInput}, transform::day};
+use aoc_2025::{
+    bench,
+    http::Client,
+    error::AocError,
+    extract::{config::{Command, Config, USAGE}, input::PuzzleInput, page},
+    load::{output::{self, Answer, Format, PuzzleOutput}, readme, report, submissions::SubmissionLog, submit::{Submitter, Verdict}},
+    runner::{self, PartRun},
+    scaffold,
+    watch,
+    transform::{day, solver::{Part, Puzzle}, trace::Tracer},
+};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     // Extract
-    let config = Config::new(&args).unwrap_or_else(|err| {
+    let config = Config::load(&args).unwrap_or_else(|err| {
         println!("Could not parse arguments into valid input: {err}");
+        println!("Run `aoc-2025 --help` to see the available commands.");
         process::exit(1);
     });
+    let tracer = runner::tracer(&config).unwrap_or_else(|err| {
+        println!("Unable to open the trace file: {err}");
+        process::exit(1);
+    });
+
+    match config.command {
+        Command::Run | Command::Test if config.watch => watch(&config, &tracer),
+        Command::Run if config.day.is_none() => run_all(&config, &tracer),
+        Command::Run => run(&config, &tracer),
+        Command::Test => test(&config, &tracer),
+        Command::Verify => verify(&config, &tracer),
+        Command::Profiles => profiles(&config, &tracer),
+        Command::List => list(&config),
+        Command::Readme => update_readme(&config, &tracer),
+        Command::Help => println!("{USAGE}"),
+        Command::Bench => benchmark(&config),
+        Command::Submit => submit(&config, &tracer),
+        Command::Import => import(&config),
+        Command::New => new_day(&config),
+    }
+}
+
+fn run(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let (puzzle, parts) = puzzle_and_parts(config);
+
+    let input = PuzzleInput::new(config).unwrap_or_else(|err| {
+        match err {
+            AocError::MissingInput(path) if config.profile.is_some() => {
+                println!("No input found at {}; save the input of that profile there first.", path.display())
+            }
+            AocError::MissingInput(path) => {
+                println!("No input found at {}; save your puzzle input there first, or use --download.", path.display())
+            }
+            err => println!("Unable to correctly read input: {err}"),
+        }
+        process::exit(1);
+    });
+
+    let answers = PuzzleInput::answers_for_day(config, input.day).unwrap_or_default();
+
+    // Transform, parsing the input once for all the parts
+    let mut runs = runner::run_parts(&input, &parts, config.solver_timeout, tracer);
+
+    // Load
+    if config.format == Format::Text && runs.len() > 1 {
+        println!("Day {}: {}", input.day, puzzle.title());
+    }
+    for run in &mut runs {
+        run.expected = answers.get(run.part).map(str::to_string);
+        if config.format != Format::Text {
+            continue;
+        }
+        match &run.outcome {
+            Ok(solution) if parts.len() > 1 => solution.show_part(run.part),
+            Ok(solution) => solution.show(),
+            Err(err) => println!("Unable to correctly process part {} of the input: {err}", run.part.number()),
+        }
+    }
+
+    if config.format != Format::Text {
+        print!("{}", output::render(&runs, config.format));
+    }
+    record(config, &runs);
+
+    if runs.iter().any(|run| run.outcome.is_err()) {
+        process::exit(1);
+    }
+}
+
+/// The puzzle of `--day`, with the parts to solve: `--part`, or else all of
+/// them.
+fn puzzle_and_parts(config: &Config) -> (&'static dyn Puzzle, Vec<Part>) {
+    let puzzle = config.day.and_then(|number| day::find(config.year, number)).unwrap_or_else(|| {
+        println!("Unable to correctly process the input: {}", AocError::UnknownDay { year: config.year, day: config.day.unwrap_or_default() });
+        process::exit(1);
+    });
+    let parts = match config.part {
+        Some(part) => vec![part],
+        None => puzzle.parts().to_vec(),
+    };
+    (puzzle, parts)
+}
+
+fn run_all(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let runs = runner::run_all(config, tracer);
 
-    let input = PuzzleInput::new(&config).unwrap_or_else(|err| {
+    record(config, &runs);
+    show_summary(config, &runs);
+}
+
+fn verify(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let runs = runner::verify(config, tracer);
+
+    if runs.is_empty() {
+        println!("There are no recorded answers to verify; store them with `run --record`.");
+        return;
+    }
+
+    show_summary(config, &runs);
+}
+
+fn profiles(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let runs = runner::run_profiles(config, tracer);
+
+    if runs.is_empty() {
+        println!("There are no profile inputs; save them as day/<n>/inputs/<name> in the data directory.");
+        return;
+    }
+
+    show_summary(config, &runs);
+}
+
+fn benchmark(config: &Config) {
+    let (_, parts) = puzzle_and_parts(config);
+
+    let input = PuzzleInput::new(config).unwrap_or_else(|err| {
         println!("Unable to correctly read input: {err}");
         process::exit(1);
     });
 
-    // Transform
-    let solution = day::solve_puzzle(&input).unwrap_or_else(|err| {
-        println!("Unable to correctly process the input: {err}");
+    for part in parts {
+        let benchmark = bench::bench(&input, part, config.warmup, config.runs).unwrap_or_else(|err| {
+            println!("Unable to correctly process the input: {err}");
+            process::exit(1);
+        });
+        report::show_benchmark(&benchmark);
+    }
+}
+
+fn submit(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let (Some(number), Some(part)) = (config.day, config.part) else {
+        unreachable!("submit always has a day and a part");
+    };
+
+    let session = match config.session() {
+        Ok(Some(session)) => session,
+        Ok(None) => {
+            println!("Set AOC_SESSION, or session_file in aoc.toml, to the session cookie of your Advent of Code login to submit answers.");
+            process::exit(1);
+        }
+        Err(err) => {
+            println!("Unable to read the session token: {err}");
+            process::exit(1);
+        }
+    };
+
+    let output = match &config.answer {
+        Some(answer) => PuzzleOutput::new(Answer::parse(answer)),
+        None => {
+            let input = PuzzleInput::new(config).unwrap_or_else(|err| {
+                println!("Unable to correctly read input: {err}");
+                process::exit(1);
+            });
+            runner::run_part(&input, part, config.solver_timeout, tracer).outcome.unwrap_or_else(|err| {
+                println!("Unable to correctly process the input: {err}");
+                process::exit(1);
+            })
+        }
+    };
+
+    let mut log = SubmissionLog::for_day(config, number).unwrap_or_else(|err| {
+        println!("Unable to read the earlier submissions: {err}");
+        process::exit(1);
+    });
+    let answer = output.answer.to_string();
+    log.check(part, &answer).unwrap_or_else(|err| {
+        println!("{err}");
         process::exit(1);
     });
 
-    // Load (TODO: maybe update the value to AOC directly)
-    solution.show();
+    println!("Submitting {answer} for day {number} part {}...", part.number());
+    let verdict = Submitter::new(Client::new(&config.base_url, &session, config.request_timeout)).submit(config.year, number, part, &output).unwrap_or_else(|err| {
+        println!("Unable to submit the answer: {err}");
+        process::exit(1);
+    });
+    println!("{verdict}");
+
+    log.record(part, &answer, verdict.clone());
+    log.write(config, number).unwrap_or_else(|err| {
+        println!("Unable to record the submission: {err}");
+        process::exit(1);
+    });
+
+    if !matches!(verdict, Verdict::Correct | Verdict::AlreadySolved) {
+        process::exit(1);
+    }
+}
+
+fn import(config: &Config) {
+    let (Some(number), Some(path)) = (config.day, &config.page) else {
+        unreachable!("import always has a day and a page");
+    };
+
+    let examples = fs::read_to_string(path)
+        .map_err(AocError::from)
+        .and_then(|html| page::examples(&html))
+        .and_then(|examples| page::save_examples(config, number, &examples))
+        .unwrap_or_else(|err| {
+            println!("Unable to import the examples of {}: {err}", path.display());
+            process::exit(1);
+        });
+
+    for example in examples {
+        println!("Saved {} with its answers", example.display());
+    }
+}
+
+fn new_day(config: &Config) {
+    let number = config.day.unwrap_or_default();
+    let source_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+    let changed = scaffold::new_day(config, &source_dir, number).unwrap_or_else(|err| {
+        println!("Unable to create day {number}: {err}");
+        process::exit(1);
+    });
+
+    for path in changed {
+        println!("Wrote {}", path.display());
+    }
+    println!("Paste the example of day {number} into its test file, or import it with `aoc-2025 import {number} --page <path>`.");
+}
+
+fn record(config: &Config, runs: &[PartRun]) {
+    if !config.record {
+        return;
+    }
+    runner::record(config, runs).unwrap_or_else(|err| {
+        println!("Unable to record the answers: {err}");
+        process::exit(1);
+    });
+}
+
+fn test(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let runs = match config.day {
+        Some(number) => runner::run_examples(config, number, tracer).unwrap_or_else(|err| {
+            println!("Unable to correctly read the examples: {err}");
+            process::exit(1);
+        }),
+        None => runner::run_all_examples(config, tracer),
+    };
+
+    show_summary(config, &runs);
+}
+
+fn watch(config: &Config, tracer: &Arc<dyn Tracer>) -> ! {
+    let number = config.day.unwrap_or_default();
+    println!("Watching the data files of day {number}; press Ctrl-C to stop.");
+
+    watch::watch(config, number, || {
+        let runs = match config.command {
+            Command::Test => runner::run_examples(config, number, tracer)
+                .unwrap_or_else(|err| vec![PartRun::failed(config.year, number, Part::One, "test", err)]),
+            _ => runner::run_all(config, tracer),
+        };
+        match config.format {
+            Format::Text => report::show_summary(&runs),
+            format => print!("{}", output::render(&runs, format)),
+        }
+    })
+}
+
+fn show_summary(config: &Config, runs: &[PartRun]) {
+    match config.format {
+        Format::Text => report::show_summary(runs),
+        format => print!("{}", output::render(runs, format)),
+    }
+
+    if runs.iter().any(|run| !run.is_ok()) {
+        process::exit(1);
+    }
+}
+
+fn update_readme(config: &Config, tracer: &Arc<dyn Tracer>) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+    let runs = runner::run_all(config, tracer);
+
+    let text = fs::read_to_string(&path).unwrap_or_else(|err| {
+        println!("Unable to read {}: {err}", path.display());
+        process::exit(1);
+    });
+    fs::write(&path, readme::replace_calendar(&text, &readme::calendar(config.year, &runs))).unwrap_or_else(|err| {
+        println!("Unable to write {}: {err}", path.display());
+        process::exit(1);
+    });
+
+    let stars = runs.iter().filter(|run| run.status() == runner::Status::Correct).count();
+    println!("Updated the calendar of {} with {stars} stars", path.display());
+}
+
+fn list(config: &Config) {
+    if day::days(config.year).next().is_none() {
+        println!("No days of {} are available yet; create one with `aoc-2025 new <day> --year {}`.", config.year, config.year);
+        return;
+    }
+    for (number, puzzle) in day::days(config.year) {
+        let parts: Vec<String> = puzzle.parts().iter().map(|part| part.number().to_string()).collect();
+        println!("Day {number:>2}: {} (parts {})", puzzle.title(), parts.join(", "));
+    }
 }
@@ -0,0 +1,422 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::extract::{answers::Answers, config::Config, input::PuzzleInput};
+use crate::load::output::PuzzleOutput;
+use crate::transform::{day, solver::{Parsed, Part}, trace::{Silent, Trace, Tracer}};
+
+/// The outcome of solving one part of one day, with the time spent solving it.
+pub struct PartRun {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// Name of the input that was solved, e.g. `input` or `test`.
+    pub input: String,
+    /// Time spent solving the part, without parsing its input.
+    pub elapsed: Duration,
+    /// Time spent parsing the input, on the run that parsed it. Runs that
+    /// reuse the parse of an earlier part, or never got to parse, have none.
+    pub parse: Option<Duration>,
+    pub outcome: Result<PuzzleOutput, AocError>,
+    /// The answer this part should produce, when it is known.
+    pub expected: Option<String>,
+    /// Fingerprint of the input text, when it could be read.
+    pub fingerprint: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Solved, with no known answer to compare against.
+    Solved,
+    /// Solved and matching the expected answer.
+    Correct,
+    /// Solved but different from the expected answer.
+    Wrong,
+    /// The solver returned an error.
+    Failed,
+    /// The solver panicked.
+    Panicked,
+    /// The solver did not finish within the timeout.
+    TimedOut,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Solved => "ok",
+            Status::Correct => "PASS",
+            Status::Wrong => "WRONG",
+            Status::Failed => "FAIL",
+            Status::Panicked => "PANIC",
+            Status::TimedOut => "TIMEOUT",
+        };
+        f.pad(status)
+    }
+}
+
+impl PartRun {
+    pub fn status(&self) -> Status {
+        match (&self.outcome, &self.expected) {
+            (Err(AocError::Panicked(_)), _) => Status::Panicked,
+            (Err(AocError::TimedOut(_)), _) => Status::TimedOut,
+            (Err(_), _) => Status::Failed,
+            (Ok(_), None) => Status::Solved,
+            (Ok(output), Some(expected)) if output.answer.to_string() == *expected => Status::Correct,
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+
+    /// A part that could not be attempted, e.g. because its input is missing.
+    pub fn failed(year: u16, day: u8, part: Part, input: &str, err: AocError) -> PartRun {
+        PartRun {
+            year,
+            day,
+            part,
+            input: input.to_string(),
+            elapsed: Duration::ZERO,
+            parse: None,
+            outcome: Err(err),
+            expected: None,
+            fingerprint: None,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status(), Status::Solved | Status::Correct)
+    }
+}
+
+/// Solves `part` of `input` on a worker thread, so a solver that panics or
+/// runs past `timeout` is reported in its `PartRun` instead of stopping the
+/// whole run.
+pub fn run_part(input: &PuzzleInput, part: Part, timeout: Option<Duration>, tracer: &Arc<dyn Tracer>) -> PartRun {
+    run_parts(input, &[part], timeout, tracer).remove(0)
+}
+
+/// Solves `parts` of `input` from a single parse of it, which the parts
+/// share, each on its own worker thread as in `run_part`. The parse time is
+/// kept on the run of the first part. When parsing fails, only the first
+/// part is reported, with the parse error. The steps of each part go to
+/// `tracer`, after a line naming the part.
+pub fn run_parts(input: &PuzzleInput, parts: &[Part], timeout: Option<Duration>, tracer: &Arc<dyn Tracer>) -> Vec<PartRun> {
+    let worker_input = input.clone();
+    let (parsed, parse_time) = isolated(timeout, move || day::parse_puzzle(&worker_input));
+    let parsed: Arc<dyn Parsed> = match parsed {
+        Ok(parsed) => Arc::from(parsed),
+        Err(err) => return parts.first().map(|&part| part_run(input, part, Err(err), Duration::ZERO, Some(parse_time))).into_iter().collect(),
+    };
+    let supported = day::find(input.year, input.day).map_or(&[][..], |puzzle| puzzle.parts());
+
+    parts.iter().enumerate().map(|(i, &part)| {
+        let (outcome, elapsed) = if supported.contains(&part) {
+            tracer.step(format_args!("== day {} part {} ({})", input.day, part.number(), input.name));
+            let (parsed, tracer) = (Arc::clone(&parsed), Arc::clone(tracer));
+            isolated(timeout, move || parsed.trace(part, tracer.as_ref()))
+        } else {
+            (Err(AocError::UnsupportedPart { day: input.day, part: part.number() }), Duration::ZERO)
+        };
+        part_run(input, part, outcome, elapsed, (i == 0).then_some(parse_time))
+    }).collect()
+}
+
+fn part_run(input: &PuzzleInput, part: Part, outcome: Result<PuzzleOutput, AocError>, elapsed: Duration, parse: Option<Duration>) -> PartRun {
+    PartRun {
+        year: input.year,
+        day: input.day,
+        part,
+        input: input.name.clone(),
+        elapsed,
+        parse,
+        outcome,
+        expected: None,
+        fingerprint: Some(input.fingerprint()),
+    }
+}
+
+/// Runs `solve` on its own thread, catching its panics and giving up on it
+/// after `timeout`. A solver that times out cannot be stopped, so its thread
+/// is left behind until the process exits.
+pub fn isolated<T: Send + 'static>(
+    timeout: Option<Duration>,
+    solve: impl FnOnce() -> Result<T, AocError> + Send + 'static,
+) -> (Result<T, AocError>, Duration) {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let start = Instant::now();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(solve))
+            .unwrap_or_else(|payload| Err(AocError::Panicked(panic_message(payload.as_ref()))));
+        // The receiver is gone when the solver timed out; nobody is waiting.
+        let _ = sender.send((outcome, start.elapsed()));
+    });
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|_| timeout),
+        None => receiver.recv().map_err(|_| Duration::ZERO),
+    };
+    received.unwrap_or_else(|waited| (Err(AocError::TimedOut(waited)), waited))
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload.downcast_ref::<&str>().map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// The tracer asked for with `--trace` and `--trace-file`: the steps go to
+/// the trace file, created anew, or to stderr. Silent without `--trace`.
+pub fn tracer(config: &Config) -> Result<Arc<dyn Tracer>, AocError> {
+    Ok(match (config.trace, &config.trace_file) {
+        (Some(level), Some(path)) => Arc::new(Trace::file(level, path)?),
+        (Some(level), None) => Arc::new(Trace::stderr(level)),
+        (None, _) => Arc::new(Silent),
+    })
+}
+
+fn selected_parts(config: &Config, number: u8) -> Vec<Part> {
+    day::find(config.year, number).map_or(vec![], |puzzle| {
+        puzzle.parts().iter().copied()
+            .filter(|&part| config.part.is_none_or(|selected| selected == part))
+            .collect()
+    })
+}
+
+/// Solves every registered day of `config.year`, or only `config.part` of
+/// each when it is set, comparing against the recorded answers of each
+/// input. A failing day does not stop the others; its error is kept in its
+/// `PartRun`.
+pub fn run_all(config: &Config, tracer: &Arc<dyn Tracer>) -> Vec<PartRun> {
+    run_days(config, false, tracer)
+}
+
+/// Solves every part that has a recorded answer, or only those of
+/// `config.day` when it is set, to catch answers that changed.
+pub fn verify(config: &Config, tracer: &Arc<dyn Tracer>) -> Vec<PartRun> {
+    run_days(config, true, tracer)
+}
+
+fn run_days(config: &Config, only_recorded: bool, tracer: &Arc<dyn Tracer>) -> Vec<PartRun> {
+    let mut runs = vec![];
+
+    for (number, _) in day::days(config.year) {
+        if config.day.is_some_and(|selected| selected != number) {
+            continue;
+        }
+        let parts = selected_parts(config, number);
+
+        let answers = match PuzzleInput::answers_for_day(config, number) {
+            Ok(answers) => answers,
+            Err(err) => {
+                runs.extend(parts.first().map(|&part| PartRun::failed(config.year, number, part, "input", err)));
+                continue;
+            }
+        };
+
+        let parts: Vec<Part> = parts.into_iter()
+            .filter(|&part| !only_recorded || answers.get(part).is_some())
+            .collect();
+        let Some(&first) = parts.first() else {
+            continue;
+        };
+
+        match PuzzleInput::for_day(config, number) {
+            Ok(input) => runs.extend(run_parts(&input, &parts, config.solver_timeout, tracer).into_iter().map(|run| {
+                PartRun { expected: answers.get(run.part).map(str::to_string), ..run }
+            })),
+            Err(err) => runs.push(PartRun::failed(config.year, number, first, "input", err)),
+        }
+    }
+
+    runs
+}
+
+/// Solves every profile input of `config.day`, or of every registered day
+/// when it is not set, comparing each against the answers recorded for it.
+pub fn run_profiles(config: &Config, tracer: &Arc<dyn Tracer>) -> Vec<PartRun> {
+    let mut runs = vec![];
+
+    for (number, _) in day::days(config.year) {
+        if config.day.is_some_and(|selected| selected != number) {
+            continue;
+        }
+        let profiles = match PuzzleInput::profiles(config, number) {
+            Ok(profiles) => profiles,
+            Err(err) => {
+                runs.push(PartRun::failed(config.year, number, Part::One, "inputs", err));
+                continue;
+            }
+        };
+
+        for profile in profiles {
+            let (input, answers) = match PuzzleInput::for_profile(config, number, &profile) {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    runs.push(PartRun::failed(config.year, number, Part::One, &profile, err));
+                    continue;
+                }
+            };
+            let parts = selected_parts(config, number);
+            runs.extend(run_parts(&input, &parts, config.solver_timeout, tracer).into_iter().map(|run| {
+                PartRun { expected: answers.get(run.part).map(str::to_string), ..run }
+            }));
+        }
+    }
+
+    runs
+}
+
+/// Solves the examples of one day and compares them with their stored
+/// answers. When an example lists answers only for some parts, the other
+/// parts are skipped, since examples often differ between parts.
+pub fn run_examples(config: &Config, number: u8, tracer: &Arc<dyn Tracer>) -> Result<Vec<PartRun>, AocError> {
+    let mut runs = vec![];
+
+    for (input, answers) in PuzzleInput::examples(config, number)? {
+        let parts: Vec<Part> = selected_parts(config, number).into_iter()
+            .filter(|&part| answers.is_empty() || answers.get(part).is_some())
+            .collect();
+        runs.extend(run_parts(&input, &parts, config.solver_timeout, tracer).into_iter().map(|run| {
+            PartRun { expected: answers.get(run.part).map(str::to_string), ..run }
+        }));
+    }
+
+    Ok(runs)
+}
+
+/// Solves the examples of every registered day of `config.year` that has some.
+pub fn run_all_examples(config: &Config, tracer: &Arc<dyn Tracer>) -> Vec<PartRun> {
+    let mut runs = vec![];
+
+    for (number, _) in day::days(config.year) {
+        match run_examples(config, number, tracer) {
+            Ok(day_runs) => runs.extend(day_runs),
+            Err(AocError::MissingInput(_)) => {}
+            // The examples could not be read at all, so the error is reported
+            // once for the day rather than for each part.
+            Err(err) => runs.push(PartRun::failed(config.year, number, Part::One, "test", err)),
+        }
+    }
+
+    runs
+}
+
+/// Stores the answers of the successful `runs` next to their inputs, keeping
+/// any answer already recorded for the other part. An answer recorded
+/// differently is only replaced with `--force`; otherwise nothing is written.
+pub fn record(config: &Config, runs: &[PartRun]) -> Result<(), AocError> {
+    let mut updates = Vec::new();
+    for (number, _) in day::days(config.year) {
+        let solved: Vec<(Part, String)> = runs.iter()
+            .filter(|run| run.year == config.year && run.day == number)
+            .filter_map(|run| run.outcome.as_ref().ok().map(|output| (run.part, output.answer.to_string())))
+            .collect();
+        if solved.is_empty() {
+            continue;
+        }
+
+        let path = PuzzleInput::path_for_day(config, number)
+            .ok_or_else(|| AocError::BadArguments("--record needs the input to come from a file".to_string()))?;
+        let mut answers = Answers::for_input(&path)?;
+        for (part, answer) in solved {
+            if let Some(recorded) = answers.get(part) && recorded != answer && !config.force {
+                return Err(AocError::BadArguments(format!(
+                    "day {number} part {} is recorded as {recorded}, not {answer}; use --force to replace it",
+                    part.number()
+                )));
+            }
+            answers.set(part, &answer);
+        }
+        updates.push((path, answers));
+    }
+
+    for (path, answers) in updates {
+        answers.write(&path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    
+    #[test]
+    fn isolated_catches_panics(){
+        let (outcome, _) = isolated(None, || -> Result<(), AocError> { panic!("unknown operator %") });
+        assert!(matches!(outcome, Err(AocError::Panicked(message)) if message == "unknown operator %"));
+
+        let (outcome, _) = isolated(None, || Ok(3));
+        assert_eq!(3, outcome.unwrap());
+    }
+
+    #[test]
+    fn isolated_gives_up_after_timeout(){
+        let timeout = Duration::from_millis(20);
+        let (outcome, elapsed) = isolated(Some(timeout), || {
+            thread::sleep(Duration::from_secs(2));
+            Ok(())
+        });
+        assert!(matches!(outcome, Err(AocError::TimedOut(waited)) if waited == timeout));
+        assert_eq!(timeout, elapsed);
+    }
+
+    #[test]
+    fn status_tells_panics_and_timeouts_apart(){
+        let run = PartRun::failed(2025, 6, Part::One, "input", AocError::Panicked("boom".to_string()));
+        assert_eq!(Status::Panicked, run.status());
+        let run = PartRun::failed(2025, 2, Part::Two, "input", AocError::TimedOut(Duration::from_secs(1)));
+        assert_eq!("TIMEOUT", run.status().to_string());
+        assert!(!run.is_ok());
+    }
+
+    #[test]
+    fn run_parts_solves_both_parts_from_one_parse(){
+        let silent: Arc<dyn Tracer> = Arc::new(Silent);
+        let input = PuzzleInput { year: 2025, day: 1, name: "test".to_string(), text: "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82".to_string() };
+        let runs = run_parts(&input, &[Part::One, Part::Two], None, &silent);
+        let answers: Vec<(Part, String)> = runs.iter()
+            .map(|run| (run.part, run.outcome.as_ref().unwrap().answer.to_string()))
+            .collect();
+        assert_eq!(vec![(Part::One, "3".to_string()), (Part::Two, "6".to_string())], answers);
+        assert!(runs[0].parse.is_some());
+        assert_eq!(None, runs[1].parse);
+
+        let input = PuzzleInput { text: "L68\nX30".to_string(), ..input };
+        let runs = run_parts(&input, &[Part::One, Part::Two], None, &silent);
+        assert_eq!(1, runs.len());
+        assert!(matches!(runs[0].outcome, Err(AocError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn record_keeps_a_differing_answer_without_force(){
+        let data_dir = std::env::temp_dir().join(format!("aoc-2025-record-{}", std::process::id()));
+        let input = data_dir.join("day/1/input");
+        fs::create_dir_all(input.parent().unwrap()).unwrap();
+        fs::write(&input, "L68").unwrap();
+        let mut answers = Answers::default();
+        answers.set(Part::One, "4");
+        answers.write(&input).unwrap();
+
+        let runs = [PartRun {
+            outcome: Ok(PuzzleOutput::new(3u64)),
+            ..PartRun::failed(2025, 1, Part::One, "input", AocError::Overflow("adding up the clicks"))
+        }];
+        let args = |extra: &str| format!("aoc-2025 run 1 --record {extra} --data-dir {}", data_dir.display())
+            .split_whitespace().map(str::to_string).collect::<Vec<_>>();
+
+        let config = Config::new(&args("")).unwrap();
+        assert!(matches!(record(&config, &runs), Err(AocError::BadArguments(_))));
+        assert_eq!(Some("4"), Answers::for_input(&input).unwrap().get(Part::One));
+
+        let config = Config::new(&args("--force")).unwrap();
+        record(&config, &runs).unwrap();
+        assert_eq!(Some("3"), Answers::for_input(&input).unwrap().get(Part::One));
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::extract::config::{Config, DEFAULT_YEAR};
+use crate::extract::input::PuzzleInput;
+
+/// Module names of the days, which follow the day number spelled out.
+const NAMES: [&str; 25] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen",
+    "seventeen", "eighteen", "nineteen", "twenty", "twenty_one", "twenty_two",
+    "twenty_three", "twenty_four", "twenty_five",
+];
+
+/// Starting point of every new day. `{day}` is replaced by the day number.
+const TEMPLATE: &str = r#"use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
+
+fn parse_content_to_puzzle_input(content: &str) -> Result<Vec<String>, AocError> {
+    Ok(content.lines().map(str::to_string).collect())
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Vec<String>;
+
+    fn title(&self) -> &'static str {
+        "Day {day}"
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<String>, AocError> {
+        parse_content_to_puzzle_input(content)
+    }
+
+    fn part_one(&self, _lines: &Vec<String>, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        Err(AocError::Unwritten { day: {day}, part: 1 })
+    }
+
+    fn part_two(&self, _lines: &Vec<String>, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        Err(AocError::Unwritten { day: {day}, part: 2 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::trace::Silent;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "the example of day {day} is not filled in yet"]
+    fn fst_passes_input_example(){
+        let result = Solution.part_one(&Solution.parse(EXAMPLE).unwrap(), &Silent).unwrap();
+        assert_eq!("", result.answer.to_string());
+    }
+}
+"#;
+
+/// The module of a day, e.g. `seven`, prefixed by its year when it is not
+/// from 2025, e.g. `y2024_seven`.
+pub fn module_name(year: u16, day: u8) -> Option<String> {
+    let name = NAMES.get(usize::from(day).checked_sub(1)?)?;
+    if year == DEFAULT_YEAR {
+        Some(name.to_string())
+    } else {
+        Some(format!("y{year}_{name}"))
+    }
+}
+
+/// The year and day of a module named by `module_name`.
+fn module_key(module: &str) -> Option<(u16, u8)> {
+    let (year, name) = match module.strip_prefix('y').and_then(|rest| rest.split_once('_')) {
+        Some((year, name)) if year.chars().all(|c| c.is_ascii_digit()) => (year.parse().ok()?, name),
+        _ => (DEFAULT_YEAR, module),
+    };
+    let day = NAMES.iter().position(|known| *known == name)? as u8 + 1;
+    Some((year, day))
+}
+
+/// Creates the module of `day` of `config.year` under `source_dir` from the
+/// template, lists it in the registry and creates its data directory with an
+/// empty example to fill in. Returns every file created or changed.
+pub fn new_day(config: &Config, source_dir: &Path, day: u8) -> Result<Vec<PathBuf>, AocError> {
+    let name = module_name(config.year, day).ok_or(AocError::UnknownDay { year: config.year, day })?;
+    let registry_path = source_dir.join("transform").join("day.rs");
+    let module_path = source_dir.join("transform").join("day").join(format!("{name}.rs"));
+
+    if module_path.exists() {
+        return Err(AocError::BadArguments(format!("{} already exists", module_path.display())));
+    }
+    let registry = register(&fs::read_to_string(&registry_path)?, config.year, day)?;
+
+    fs::write(&module_path, TEMPLATE.replace("{day}", &day.to_string()))?;
+    fs::write(&registry_path, registry)?;
+    let mut changed = vec![module_path, registry_path];
+
+    let day_dir = PuzzleInput::day_directory(config, day);
+    fs::create_dir_all(&day_dir)?;
+    for (file, content) in [("test", ""), ("test.answers", "# 1: <answer of the example>\n# 2: <answer of the example>\n")] {
+        let path = day_dir.join(file);
+        if !path.exists() {
+            fs::write(&path, content)?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Adds the `pub mod` line and the registry entry of `day` of `year` to the
+/// source of `transform/day.rs`, keeping both sorted by year and day.
+pub fn register(source: &str, year: u16, day: u8) -> Result<String, AocError> {
+    let name = module_name(year, day).ok_or(AocError::UnknownDay { year, day })?;
+    let key = (year, day);
+
+    let module_of = |line: &str| module_key(line.strip_prefix("pub mod ")?.strip_suffix(';')?);
+    let entry_of = |line: &str| {
+        let mut fields = line.trim().strip_prefix('(')?.split(", ");
+        Some((fields.next()?.parse::<u16>().ok()?, fields.next()?.parse::<u8>().ok()?))
+    };
+
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.iter().any(|line| module_of(line) == Some(key)) {
+        return Err(AocError::BadArguments(format!("day {day} of {year} is already registered")));
+    }
+
+    let last_module = lines.iter().rposition(|line| module_of(line).is_some())
+        .ok_or_else(|| AocError::BadArguments("no day modules found in day.rs".to_string()))?;
+    let registry_end = lines.iter().position(|line| *line == "];")
+        .ok_or_else(|| AocError::BadArguments("no REGISTRY found in day.rs".to_string()))?;
+
+    let module_at = lines.iter().position(|line| module_of(line).is_some_and(|other| other > key))
+        .unwrap_or(last_module + 1);
+    let entry_at = lines.iter().position(|line| entry_of(line).is_some_and(|other| other > key))
+        .filter(|&index| index < registry_end)
+        .unwrap_or(registry_end);
+
+    let module_line = format!("pub mod {name};");
+    let entry_line = format!("    ({year}, {day}, &{name}::Solution),");
+
+    let mut result = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index == module_at {
+            result.push_str(&module_line);
+            result.push('\n');
+        }
+        if index == entry_at {
+            result.push_str(&entry_line);
+            result.push('\n');
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_RS: &str = "\
+pub mod one;
+pub mod three;
+
+static REGISTRY: &[(u16, u8, &dyn Puzzle)] = &[
+    (2025, 1, &one::Solution),
+    (2025, 3, &three::Solution),
+];
+";
+
+    #[test]
+    fn registers_days_in_order(){
+        let source = register(DAY_RS, 2025, 2).unwrap();
+        assert_eq!("\
+pub mod one;
+pub mod two;
+pub mod three;
+
+static REGISTRY: &[(u16, u8, &dyn Puzzle)] = &[
+    (2025, 1, &one::Solution),
+    (2025, 2, &two::Solution),
+    (2025, 3, &three::Solution),
+];
+", source);
+
+        let source = register(DAY_RS, 2025, 7).unwrap();
+        assert!(source.contains("pub mod three;\npub mod seven;\n"));
+        assert!(source.contains("    (2025, 3, &three::Solution),\n    (2025, 7, &seven::Solution),\n];"));
+    }
+
+    #[test]
+    fn other_years_come_first_or_last(){
+        let source = register(DAY_RS, 2024, 25).unwrap();
+        assert!(source.starts_with("pub mod y2024_twenty_five;\npub mod one;\n"));
+        assert!(source.contains("[\n    (2024, 25, &y2024_twenty_five::Solution),\n    (2025, 1, &one::Solution),"));
+
+        let source = register(&source, 2026, 1).unwrap();
+        assert!(source.contains("pub mod three;\npub mod y2026_one;\n"));
+        assert!(source.contains("    (2025, 3, &three::Solution),\n    (2026, 1, &y2026_one::Solution),\n];"));
+    }
+
+    #[test]
+    fn refuses_registered_and_unknown_days(){
+        assert!(matches!(register(DAY_RS, 2025, 3), Err(AocError::BadArguments(_))));
+        assert!(matches!(register(DAY_RS, 2025, 26), Err(AocError::UnknownDay { year: 2025, day: 26 })));
+        assert_eq!(Some("twelve".to_string()), module_name(2025, 12));
+        assert_eq!(Some("y2024_nine".to_string()), module_name(2024, 9));
+        assert_eq!(None, module_name(2025, 0));
+    }
+}
@@ -1,20 +1,79 @@
-use crate::{extract::input::PuzzleInput, load::output::PuzzleOutput};
-
-mod one;
-mod two;
-mod three;
-mod four;
-mod five;
-mod six;
-
-pub fn solve_puzzle(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input.day {
-        1 => one::solve(input),
-        2 => two::solve(input),
-        3 => three::solve(input),
-        4 => four::solve(input),
-        5 => five::solve(input),
-        6 => six::solve(input),
-        n => Err(format!("No solution available for day {number}", number=n).to_string())
+use crate::{error::AocError, extract::input::PuzzleInput, load::output::PuzzleOutput};
+use crate::transform::solver::{Part, Parsed, Puzzle};
+
+pub mod one;
+pub mod two;
+pub mod three;
+pub mod four;
+pub mod five;
+pub mod six;
+
+/// Every implemented day with its year, sorted by year and day. Adding a day
+/// only requires implementing `Solver` in its module and listing it here,
+/// which the `new` command does for you.
+static REGISTRY: &[(u16, u8, &dyn Puzzle)] = &[
+    (2025, 1, &one::Solution),
+    (2025, 2, &two::Solution),
+    (2025, 3, &three::Solution),
+    (2025, 4, &four::Solution),
+    (2025, 5, &five::Solution),
+    (2025, 6, &six::Solution),
+];
+
+pub fn registry() -> &'static [(u16, u8, &'static dyn Puzzle)] {
+    REGISTRY
+}
+
+/// The implemented days of `year`, sorted by day.
+pub fn days(year: u16) -> impl Iterator<Item = (u8, &'static dyn Puzzle)> {
+    REGISTRY.iter().filter(move |(y, _, _)| *y == year).map(|(_, day, puzzle)| (*day, *puzzle))
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static dyn Puzzle> {
+    REGISTRY.iter().find(|(y, n, _)| *y == year && *n == day).map(|(_, _, puzzle)| *puzzle)
+}
+
+/// Solves `part` of `day` of `year` for the puzzle input `text`.
+pub fn solve(year: u16, day: u8, part: Part, text: &str) -> Result<PuzzleOutput, AocError> {
+    let puzzle = find(year, day).ok_or(AocError::UnknownDay { year, day })?;
+    if !puzzle.parts().contains(&part) {
+        return Err(AocError::UnsupportedPart { day, part: part.number() });
+    }
+    puzzle.solve(text, part)
+}
+
+/// Parses the puzzle input `text` of `day` of `year` once, for all parts.
+pub fn parse(year: u16, day: u8, text: &str) -> Result<Box<dyn Parsed>, AocError> {
+    find(year, day).ok_or(AocError::UnknownDay { year, day })?.parse(text)
+}
+
+pub fn parse_puzzle(input: &PuzzleInput) -> Result<Box<dyn Parsed>, AocError> {
+    parse(input.year, input.day, &input.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_sorted_by_day(){
+        let days: Vec<u8> = days(2025).map(|(day, _)| day).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], days[..6]);
+        let keys: Vec<(u16, u8)> = registry().iter().map(|(year, day, _)| (*year, *day)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn find_returns_none_for_missing_day(){
+        assert!(find(2025, 25).is_none());
+        assert!(find(2024, 1).is_none());
+        assert_eq!("Secret Entrance", find(2025, 1).unwrap().title());
+    }
+
+    #[test]
+    fn parse_puzzle_reports_unknown_day(){
+        let input = PuzzleInput { year: 2025, day: 25, name: "input".to_string(), text: String::new() };
+        assert!(matches!(parse_puzzle(&input), Err(AocError::UnknownDay { year: 2025, day: 25 })));
+        assert!(matches!(solve(2024, 1, Part::One, "L68"), Err(AocError::UnknownDay { year: 2024, day: 1 })));
     }
 }
@@ -1,6 +1,12 @@
 use std::str::FromStr;
 
-use crate::{extract::input::PuzzleInput, load::output::PuzzleOutput};
+use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
+
+/// The fresh ingredient id ranges followed by the available ingredient ids.
+pub type Inventory = (Vec<(i64, i64)>, Vec<i64>);
 
 struct FreshnessChecker {
     fresh_ranges: Vec<i64> 
@@ -9,9 +15,9 @@ struct FreshnessChecker {
 /// Helper class to check if an ingredient is fresh or not. We rely on binary
 /// search to perform freshness checks in a more peformant way.
 impl FreshnessChecker {
-    pub fn new(fresh_ingredients_ranges: &Vec<(i64, i64)>) -> FreshnessChecker {
+    pub fn new(fresh_ingredients_ranges: &[(i64, i64)], tracer: &dyn Tracer) -> FreshnessChecker {
         let mut ret = FreshnessChecker { fresh_ranges: vec![] };
-        ret.initialize(fresh_ingredients_ranges);
+        ret.initialize(fresh_ingredients_ranges, tracer);
         ret
     }
 
@@ -21,7 +27,7 @@ impl FreshnessChecker {
     /// The resulting fresh_ranges vector is a vector of even length. Where every
     /// two values are the beggining and the end of two given intervals. Intervals
     /// are sorted from left to right.
-    fn initialize(&mut self, fresh_ingredients_ranges: &Vec<(i64, i64)>) {
+    fn initialize(&mut self, fresh_ingredients_ranges: &[(i64, i64)], tracer: &dyn Tracer) {
         for (l, r) in fresh_ingredients_ranges.iter() {
             let mut l_idx = self.fresh_ranges.partition_point(|&x| { x < *l });
             let mut r_idx = self.fresh_ranges.partition_point(|&x| { x <= *r });
@@ -50,10 +56,18 @@ impl FreshnessChecker {
 
             // Lastly we have a new interval that is [fresh_ranges[l], fresh_ranges[r]] any
             // value in between needs to be removed.
+            let is_merge = is_left_merge_required || is_right_merge_required || l_idx + 1 < r_idx;
             while l_idx + 1 < r_idx {
                 self.fresh_ranges.remove(l_idx + 1);
                 r_idx -= 1;
             }
+
+            let (merged_l, merged_r) = (self.fresh_ranges[l_idx], self.fresh_ranges[r_idx]);
+            if is_merge {
+                tracer.step(format_args!("{l}-{r} merged into {merged_l}-{merged_r}"));
+            } else {
+                tracer.detail(format_args!("{l}-{r} added"));
+            }
         }
     }
 
@@ -72,9 +86,16 @@ impl FreshnessChecker {
         }
     }
 
-    fn count_fresh_ids(&self) -> i64 {
+    /// Number of disjoint intervals left after merging the overlapping ones.
+    fn range_count(&self) -> usize {
+        self.fresh_ranges.len() / 2
+    }
+
+    fn count_fresh_ids(&self) -> Option<u64> {
         let iter = self.fresh_ranges.chunks_exact(2);
-        let count = iter.clone().map(|v| {v[1] - v[0] + 1}).sum();
+        let count = iter.clone().try_fold(0u64, |acc, v| {
+            u64::try_from(v[1].checked_sub(v[0])?).ok()?.checked_add(1)?.checked_add(acc)
+        });
 
         if !iter.remainder().is_empty() {
             panic!("Incorrect number of fresh ranges; it should be even.");
@@ -84,99 +105,138 @@ impl FreshnessChecker {
     }
 }
 
-fn decode_content_into_puzzle_input(content: String) -> (Vec<(i64,i64)>, Vec<i64>){
+fn decode_content_into_puzzle_input(content: &str) -> Result<Inventory, AocError>{
+    let parse_id = |x: &str| {
+        i64::from_str(x).map_err(|_| AocError::parse_at(content, x, "expected an integer ingredient id"))
+    };
+
     let mut splitted_content = content.trim().split("\n\n");
     let ranges = splitted_content.next().unwrap_or_default();
     let ingredients = splitted_content.next().unwrap_or_default();
     
-    let fresh_ingredients_ranges = ranges.split('\n').map(|x| {
-        let mut beg_end = x.split('-');
-        let beg = beg_end.next().expect("The range definition is empty").parse::<i64>().unwrap();
-        let end = beg_end.next().expect("The range definition is incorrect").parse::<i64>().unwrap();
-        (beg, end)
-    }).collect();
-
-    let available_ingredients = ingredients.split('\n').map(i64::from_str).map(Result::unwrap).collect();
-    (fresh_ingredients_ranges, available_ingredients)
+    let fresh_ingredients_ranges = ranges.lines().map(|x| {
+        let (beg, end) = x.split_once('-')
+            .ok_or_else(|| AocError::parse_at(content, x, "expected a range like 3-5"))?;
+        let (beg, end) = (parse_id(beg)?, parse_id(end)?);
+        if beg > end {
+            return Err(AocError::parse_at(content, x, "the range ends before it begins"));
+        }
+        Ok((beg, end))
+    }).collect::<Result<_, AocError>>()?;
+
+    let available_ingredients = ingredients.lines().map(parse_id).collect::<Result<_, AocError>>()?;
+    Ok((fresh_ingredients_ranges, available_ingredients))
 }
     
 
-fn solve_fst(content: String) -> String {
-    let (fresh_ingredients_ranges, available_ingredients) = decode_content_into_puzzle_input(content);
+pub struct Solution;
 
-    let checker = FreshnessChecker::new(&fresh_ingredients_ranges);
+impl Solver for Solution {
+    type Input = Inventory;
 
-    let count = available_ingredients.into_iter().filter(|&ingredient| {checker.is_fresh(ingredient)}).count();
+    fn title(&self) -> &'static str {
+        "Cafeteria"
+    }
 
-    count.to_string()
-}
+    fn parse(&self, content: &str) -> Result<Inventory, AocError> {
+        decode_content_into_puzzle_input(content)
+    }
 
-fn solve_snd(content: String) -> String {
-    let (fresh_ingredients_ranges, _) = decode_content_into_puzzle_input(content);
+    fn part_one(&self, (fresh_ingredients_ranges, available_ingredients): &Inventory, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let checker = FreshnessChecker::new(fresh_ingredients_ranges, tracer);
 
-    let checker = FreshnessChecker::new(&fresh_ingredients_ranges);
+        let count = available_ingredients.iter().filter(|&&ingredient| {checker.is_fresh(ingredient)}).count();
 
-    let count = checker.count_fresh_ids();
+        Ok(PuzzleOutput::new(count)
+            .with_diagnostic("ranges", fresh_ingredients_ranges.len())
+            .with_diagnostic("merged ranges", checker.range_count()))
+    }
 
-    count.to_string()
-}
+    fn part_two(&self, (fresh_ingredients_ranges, _): &Inventory, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let checker = FreshnessChecker::new(fresh_ingredients_ranges, tracer);
+
+        let count = checker.count_fresh_ids().ok_or(AocError::Overflow("counting fresh ingredient ids"))?;
 
-pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input {
-        PuzzleInput{
-            iteration: 1,
-            text,
-            ..
-        } => Ok(PuzzleOutput{result: solve_fst(text.to_string())}),
-        PuzzleInput{
-            iteration: 2,
-            text,
-            ..
-        } => Ok(PuzzleOutput{result: solve_snd(text.to_string())}),
-        _ => Err("Incorrect Puzzle Input".to_string())
-    }
-} 
+        Ok(PuzzleOutput::new(count)
+            .with_diagnostic("ranges", fresh_ingredients_ranges.len())
+            .with_diagnostic("merged ranges", checker.range_count()))
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transform::trace::{Level, Silent};
 
     #[test]
     fn fst_passes_input_example(){
-        let result = solve_fst("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32".to_string()).to_string();
-        assert_eq!("3", result);
+        let result = Solution.part_one(&Solution.parse("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32").unwrap(), &Silent).unwrap();
+        assert_eq!("3", result.answer.to_string());
+        assert_eq!(vec![("ranges", "4".to_string()), ("merged ranges", "2".to_string())], result.diagnostics);
     }
 
     #[test]
     fn decode_works_correctly(){
-        let (ranges, ingredients) = decode_content_into_puzzle_input("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32".to_string());
+        let (ranges, ingredients) = decode_content_into_puzzle_input("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32").unwrap();
         assert_eq!(vec![(3,5), (10,14), (16,20), (12, 18)], ranges);
         assert_eq!(vec![1,5,8,11,17,32], ingredients);
     }
 
+    #[test]
+    fn decode_reports_location_of_bad_input(){
+        let result = decode_content_into_puzzle_input("3-5\n10-14\n\n1\n5a");
+        assert!(matches!(result, Err(AocError::Parse { line: 5, column: 1, .. })));
+
+        let result = decode_content_into_puzzle_input("3-5\n14-10\n\n1");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 1, .. })));
+
+        let result = decode_content_into_puzzle_input("3-5\n10-x\n\n1");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 4, .. })));
+    }
+
     #[test]
     fn fresh_ranges_constructor_initializes_correctly(){
         let ranges = vec![(3,5), (10,14), (16,20), (12, 18)];
-        let ret = FreshnessChecker::new(&ranges);
+        let ret = FreshnessChecker::new(&ranges, &Silent);
         assert_eq!(vec![3,5,10,20], ret.fresh_ranges);
 
         let ranges = vec![(3,5), (10,14), (16,20), (6,7), (12, 18)];
-        let ret = FreshnessChecker::new(&ranges);
+        let ret = FreshnessChecker::new(&ranges, &Silent);
         assert_eq!(vec![3,5,6,7,10,20], ret.fresh_ranges);
 
         let ranges = vec![(3,5), (10,14), (16,20), (5,7), (12, 18)];
-        let ret = FreshnessChecker::new(&ranges);
+        let ret = FreshnessChecker::new(&ranges, &Silent);
         assert_eq!(vec![3,7,10,20], ret.fresh_ranges);
 
         let ranges = vec![(3,5), (10,14), (16,20), (9,10), (12, 18)];
-        let ret = FreshnessChecker::new(&ranges);
+        let ret = FreshnessChecker::new(&ranges, &Silent);
         assert_eq!(vec![3,5,9,20], ret.fresh_ranges);
     }
 
+    /// Keeps every event it is given.
+    struct Events(std::sync::Mutex<Vec<String>>);
+
+    impl Tracer for Events {
+        fn enabled(&self, _level: Level) -> bool {
+            true
+        }
+
+        fn event(&self, level: Level, message: std::fmt::Arguments<'_>) {
+            self.0.lock().unwrap().push(format!("{level} {message}"));
+        }
+    }
+
+    #[test]
+    fn traces_merges_of_ranges(){
+        let events = Events(Default::default());
+        FreshnessChecker::new(&[(3,5), (10,14), (16,20), (12, 18)], &events);
+        assert_eq!(vec!["detail 3-5 added", "detail 10-14 added", "detail 16-20 added", "step 12-18 merged into 10-20"], *events.0.lock().unwrap());
+    }
+
     #[test]
     fn is_fresh_correctly_works_on_input(){
         let ranges = vec![(3,5), (10,14), (16,20), (12, 18)];
-        let checker = FreshnessChecker::new(&ranges);
+        let checker = FreshnessChecker::new(&ranges, &Silent);
 
         assert!(!checker.is_fresh(1));
         assert!(checker.is_fresh(5));
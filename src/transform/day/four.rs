@@ -1,51 +1,51 @@
-use std::cmp;
+use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
 
-use crate::{extract::input::PuzzleInput, load::output::PuzzleOutput};
-
-#[derive(PartialEq, Debug)]
-enum Cell {
+#[derive(PartialEq, Debug, Clone)]
+pub enum Cell {
     Empty,
     Paper
 }
 
-fn parse_content_into_grid(content: String) -> Vec<Vec<Cell>> {
+fn parse_content_into_grid(content: &str) -> Result<Vec<Vec<Cell>>, AocError> {
     let lines = content.lines();
-    lines.map(|line| {
+    lines.enumerate().map(|(i, line)| {
         let cells = line.chars();
-        cells.map(|x| {
+        cells.enumerate().map(|(j, x)| {
             match x {
-                '@' => Cell::Paper,
-                '.' => Cell::Empty,
-                _ => panic!("Incorrect character.")
+                '@' => Ok(Cell::Paper),
+                '.' => Ok(Cell::Empty),
+                _ => Err(AocError::parse(i + 1, j + 1, &x.to_string(), "expected '@' or '.'"))
             }
         }).collect()
     }).collect()
 }
 
-fn surrounding_papers_count(content: &Vec<Vec<Cell>>, row_index: usize, col_index: usize) -> u32 {
+fn surrounding_papers_count(content: &[Vec<Cell>], row_index: usize, col_index: usize) -> u32 {
     let mut count = 0;
 
-    let num_rows = content.len();
-    let num_columns = content.first().unwrap().len();
+    let first_row = row_index.saturating_sub(1);
+    let first_col = col_index.saturating_sub(1);
 
-    for i in row_index.checked_sub(1).unwrap_or(0)..cmp::min(num_rows, row_index + 2) {
-        for j in col_index.checked_sub(1).unwrap_or(0)..cmp::min(num_columns, col_index + 2) {
-            if i != row_index || j != col_index {
-                if content[i][j] == Cell::Paper {
-                    count += 1
-                }
-             }
+    for (i, row) in content.iter().enumerate().skip(first_row).take(row_index + 2 - first_row) {
+        let cells = row.iter().enumerate().skip(first_col).take(col_index + 2 - first_col);
+        for (j, cell) in cells {
+            if (i != row_index || j != col_index) && *cell == Cell::Paper {
+                count += 1
+            }
         }
     }
     count
 }
 
-fn get_papers_that_can_be_moved(plan: &Vec<Vec<Cell>>) -> Vec<(usize, usize)> {
+fn get_papers_that_can_be_moved(plan: &[Vec<Cell>]) -> Vec<(usize, usize)> {
     let mut list_of_positions: Vec<(usize, usize)> = vec![];
 
     for (i, row) in  plan.iter().enumerate() {
         for (j, cell) in row.iter().enumerate(){
-            if let Cell::Paper = cell && surrounding_papers_count(&plan, i, j) < 4{
+            if let Cell::Paper = cell && surrounding_papers_count(plan, i, j) < 4{
                 list_of_positions.push((i,j));
             }
         }
@@ -54,67 +54,75 @@ fn get_papers_that_can_be_moved(plan: &Vec<Vec<Cell>>) -> Vec<(usize, usize)> {
     list_of_positions
 }
 
-fn remove_paper(plan: &mut Vec<Vec<Cell>>, positions: &Vec<(usize,usize)>) -> () {
+fn remove_paper(plan: &mut [Vec<Cell>], positions: &[(usize,usize)]) {
     for (i, j) in positions {
         plan[*i][*j] = Cell::Empty;
     }
 }
 
-fn solve_fst(content: String) -> String {
-    let plan = parse_content_into_grid(content);
+pub struct Solution;
 
-    format!("{}", get_papers_that_can_be_moved(&plan).len())
-}
+impl Solver for Solution {
+    type Input = Vec<Vec<Cell>>;
 
-fn solve_snd(content: String) -> String {
-    let mut total_papers_moved = 0;
-    let mut plan = parse_content_into_grid(content);
-    
-    loop {
-        let changes: Vec<(usize, usize)> = get_papers_that_can_be_moved(&plan);
+    fn title(&self) -> &'static str {
+        "Printing Department"
+    }
 
-        remove_paper(&mut plan, &changes);
+    fn parse(&self, content: &str) -> Result<Vec<Vec<Cell>>, AocError> {
+        parse_content_into_grid(content)
+    }
 
-        if changes.len() == 0 {
-            break;
-        }
-        else {
-            total_papers_moved += changes.len();
-        }
+    fn part_one(&self, plan: &Vec<Vec<Cell>>, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        Ok(PuzzleOutput::new(get_papers_that_can_be_moved(plan).len()))
     }
 
-    format!("{}", total_papers_moved)
-}
+    fn part_two(&self, plan: &Vec<Vec<Cell>>, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let mut total_papers_moved = 0;
+        let mut removals_per_round = vec![];
+        let mut plan = plan.clone();
+
+        loop {
+            let changes: Vec<(usize, usize)> = get_papers_that_can_be_moved(&plan);
 
-pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input {
-        PuzzleInput{
-            iteration: 1,
-            text,
-            ..
-        } => Ok(PuzzleOutput{result: solve_fst(text.to_string())}),
-        PuzzleInput{
-            iteration: 2,
-            text,
-            ..
-        } => Ok(PuzzleOutput{result: solve_snd(text.to_string())}),
-        _ => Err("Incorrect Puzzle Input".to_string())
+            remove_paper(&mut plan, &changes);
+
+            if changes.is_empty() {
+                break;
+            }
+            else {
+                total_papers_moved += changes.len();
+                removals_per_round.push(changes.len().to_string());
+            }
+        }
+
+        Ok(PuzzleOutput::new(total_papers_moved)
+            .with_diagnostic("rounds", removals_per_round.len())
+            .with_diagnostic("removals per round", removals_per_round.join(", ")))
     }
-} 
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transform::trace::Silent;
 
     #[test]
     fn fst_passes_input_example(){
-        let result = solve_fst("..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.".to_string());
-        assert_eq!(result, "13");
+        let result = Solution.part_one(&Solution.parse("..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.").unwrap(), &Silent).unwrap();
+        assert_eq!(result.answer.to_string(), "13");
     }
 
     #[test]
     fn snd_passes_input_example(){
-        let result = solve_snd("..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.".to_string());
-        assert_eq!(result, "43");
+        let result = Solution.part_two(&Solution.parse("..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.").unwrap(), &Silent).unwrap();
+        assert_eq!(result.answer.to_string(), "43");
+        assert_eq!(("removals per round", "13, 12, 7, 5, 2, 1, 1, 1, 1".to_string()), result.diagnostics[1]);
+    }
+
+    #[test]
+    fn parse_reports_location_of_bad_character(){
+        let result = parse_content_into_grid("..@@.\n@@@.@\n@@#@@");
+        assert!(matches!(result, Err(AocError::Parse { line: 3, column: 3, .. })));
     }
 }
@@ -1,38 +1,57 @@
-use crate::{extract::input::PuzzleInput, load::output::PuzzleOutput};
+use std::fmt;
 
-fn parse_content_to_puzzle_input(content: String) -> Vec<Movement>{
+use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
+
+fn parse_content_to_puzzle_input(content: &str) -> Result<Vec<Movement>, AocError>{
     let lines = content.lines();
-    lines.map(Movement::new).collect()
+    lines.enumerate().map(|(index, line)| Movement::new(index + 1, line)).collect()
 }
 
-enum Movement {
+/// A rotation of the dial by a number of clicks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Movement {
     Left(i32),
     Right(i32),
 }
 
 impl Movement {
-    fn new(line: &str) -> Movement {
+    fn new(line_number: usize, line: &str) -> Result<Movement, AocError> {
         let mut chars = line.chars();
-        let direction = chars.next().unwrap();
-        let number_of_steps = chars.collect::<String>().parse::<i32>().unwrap();
+        let direction = chars.next()
+            .ok_or_else(|| AocError::parse(line_number, 1, line, "expected a rotation, found an empty line"))?;
+        let steps = chars.as_str();
+        let number_of_steps = steps.parse::<i32>().ok().filter(|&n| n >= 0)
+            .ok_or_else(|| AocError::parse(line_number, 2, steps, "expected a non-negative number of steps"))?;
         if direction == 'L' {
-            Movement::Left(number_of_steps)
+            Ok(Movement::Left(number_of_steps))
         } else if direction == 'R' {
-            Movement::Right(number_of_steps)
+            Ok(Movement::Right(number_of_steps))
         } else {
-            panic!("The input line is incorrect it does not start by L or R. {}", line);
+            Err(AocError::parse(line_number, 1, line, "the rotation does not start by L or R"))
         }
     }
 
-    fn act_on_dial(&self, position: i32) -> i32 {
+    fn act_on_dial(&self, position: i32) -> Option<i32> {
         match self {
-            Movement::Left(distance) => position - distance, // Should always be > 0
-            Movement::Right(distance) => position + distance, // Should always be > 0
+            Movement::Left(distance) => position.checked_sub(*distance), // Should always be > 0
+            Movement::Right(distance) => position.checked_add(*distance), // Should always be > 0
         }
 
     }
 }
 
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Movement::Left(distance) => write!(f, "L{distance}"),
+            Movement::Right(distance) => write!(f, "R{distance}"),
+        }
+    }
+}
+
 struct Safe {
     dial_position: i32
 }
@@ -48,93 +67,112 @@ impl Safe {
         self.dial_position % 100 == 0
     }
 
-    fn turns_to_the_dial(&self) -> i32 {
-        if self.dial_position >= 0 {
-            self.dial_position / 100
-        }
-        else {
-            self.dial_position / 100 - 1
-        }
+    /// The number the dial points at, between 0 and 99.
+    fn number(&self) -> i32 {
+        self.dial_position.rem_euclid(100)
     }
 }
 
-fn move_dial(safe: &Safe, movement: Movement) -> Safe {
-    Safe {
-        dial_position: movement.act_on_dial(safe.dial_position)
-    }
+fn move_dial(safe: &Safe, movement: &Movement) -> Result<Safe, AocError> {
+    let dial_position = movement.act_on_dial(safe.dial_position)
+        .ok_or(AocError::Overflow("turning the dial"))?;
+    Ok(Safe { dial_position })
 }
 
-fn zeroes_between_safe_positions(prev_safe: &Safe, curr_safe: &Safe) -> i32{
-    let mut response = 0;
-    if prev_safe.dial_position < curr_safe.dial_position{
-        let mut next_hundred = (prev_safe.turns_to_the_dial() + 1) * 100;
-        if next_hundred == prev_safe.dial_position {
-            next_hundred += 100;
-        }
-        while next_hundred <= curr_safe.dial_position {
-            response += 1;
-            next_hundred += 100;
-        }
-    } else if curr_safe.dial_position < prev_safe.dial_position{
-        let mut prev_hundred = prev_safe.turns_to_the_dial() * 100;
-        if prev_hundred == prev_safe.dial_position {
-            prev_hundred -= 100;
-        }
-        while prev_hundred >= curr_safe.dial_position {
-            response += 1;
-            prev_hundred -= 100;
-        }
-    }
-    response
+/// The clicks that leave the dial at zero while it turns from `prev_safe` to
+/// `curr_safe`, not counting the position it starts from.
+fn zeroes_between_safe_positions(prev_safe: &Safe, curr_safe: &Safe) -> u64{
+    // Counted in i64 so rotations close to the limits of i32 cannot overflow.
+    let hundreds = |position: i64| position.div_euclid(100);
+    let (prev, curr) = (i64::from(prev_safe.dial_position), i64::from(curr_safe.dial_position));
+    let crossings = if prev < curr {
+        hundreds(curr) - hundreds(prev)
+    } else {
+        // Turning left, the hundred the dial starts on is not crossed but the
+        // one it stops on is.
+        hundreds(prev - 1) - hundreds(curr - 1)
+    };
+    crossings.unsigned_abs()
 }
 
-fn solve_fst(content: String) -> PuzzleOutput {
-    let mut safe = Safe::new();
-    let movements = parse_content_to_puzzle_input(content);
+pub struct Solution;
 
-    let mut response = 0;
+impl Solver for Solution {
+    type Input = Vec<Movement>;
 
-    for movement in movements {
-        safe = move_dial(&safe, movement);
-        if safe.is_pointing_at_zero() {
-            response += 1;
+    fn title(&self) -> &'static str {
+        "Secret Entrance"
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<Movement>, AocError> {
+        parse_content_to_puzzle_input(content)
+    }
+
+    fn part_one(&self, movements: &Vec<Movement>, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let mut safe = Safe::new();
+
+        let mut response: u64 = 0;
+
+        for movement in movements {
+            safe = move_dial(&safe, movement)?;
+            if safe.is_pointing_at_zero() {
+                response += 1;
+            }
+            tracer.step(format_args!("{movement}: dial at {}{}", safe.number(), if safe.is_pointing_at_zero() { ", at zero" } else { "" }));
         }
+
+        Ok(PuzzleOutput::new(response))
     }
 
-    PuzzleOutput { result: response.to_string() }
+    fn part_two(&self, movements: &Vec<Movement>, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let mut curr_safe_position = Safe::new();
+
+        let mut response = 0;
+
+        for movement in movements {
+            let prev_safe_position = curr_safe_position;
+            curr_safe_position = move_dial(&prev_safe_position, movement)?;
+
+            let crossings = zeroes_between_safe_positions(
+                &prev_safe_position,
+                &curr_safe_position
+            );
+            response += crossings;
+            tracer.step(format_args!("{movement}: dial at {}, {crossings} zero crossings", curr_safe_position.number()));
+        }
+
+        Ok(PuzzleOutput::new(response))
+    }
 }
 
-fn solve_snd(content: String) -> PuzzleOutput {
-    let mut curr_safe_position = Safe::new();
-    let movements = parse_content_to_puzzle_input(content);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::trace::Silent;
 
-    let mut response = 0;
+    #[test]
+    fn passes_input_example(){
+        let movements = Solution.parse("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+        assert_eq!("3", Solution.part_one(&movements, &Silent).unwrap().answer.to_string());
+        assert_eq!("6", Solution.part_two(&movements, &Silent).unwrap().answer.to_string());
+    }
 
-    for movement in movements {
-        let prev_safe_position = curr_safe_position;
-        curr_safe_position = move_dial(&prev_safe_position, movement);
+    #[test]
+    fn parse_reports_location_of_bad_rotation(){
+        let result = parse_content_to_puzzle_input("L68\nX30\nR48");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 1, .. })));
 
-        response += zeroes_between_safe_positions(
-            &prev_safe_position,
-            &curr_safe_position
-        );
+        let result = parse_content_to_puzzle_input("L68\nR4x8");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 2, .. })));
     }
 
-    PuzzleOutput { result: response.to_string() }
-}
+    #[test]
+    fn counts_crossings_of_rotations_near_the_limit(){
+        let movements = Solution.parse("R2147483597\nL2147483647").unwrap();
+        assert_eq!("1", Solution.part_one(&movements, &Silent).unwrap().answer.to_string());
+        assert_eq!("42949673", Solution.part_two(&movements, &Silent).unwrap().answer.to_string());
 
-pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input {
-        PuzzleInput {
-            day: 1,
-            iteration: 1,
-            text
-        } => Ok(solve_fst(text.to_string())),
-        PuzzleInput {
-            day: 1,
-            iteration: 2,
-            text
-        } => Ok(solve_snd(text.to_string())),
-        _ => Err("Incorrect Puzzle Input".to_string())
+        let movements = Solution.parse("R2147483597\nR1").unwrap();
+        assert!(matches!(Solution.part_two(&movements, &Silent), Err(AocError::Overflow(_))));
     }
 }
@@ -2,125 +2,196 @@ use std::str::FromStr;
 use std::string::ToString;
 use std::iter;
 
-use crate::extract::input::PuzzleInput;
+use crate::error::AocError;
 use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
+
+/// The worksheet as written. It is read in one of the two ways the puzzle
+/// describes only when a part is solved, so an input that breaks one reading
+/// still solves the other part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Worksheet {
+    text: String,
+}
+
+impl Worksheet {
+    /// Each problem's operation with its numbers laid out in rows, for part one.
+    pub fn by_rows(&self) -> Result<Vec<(String, Vec<i64>)>, AocError> {
+        decode_content_into_puzzle_input(&self.text)
+    }
+
+    /// The same problems with their numbers written top to bottom in columns
+    /// read from right to left, for part two.
+    pub fn by_columns(&self) -> Result<Vec<(String, Vec<i64>)>, AocError> {
+        decode_content_into_puzzle_input_snd(&self.text)
+    }
+}
 
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
+fn transpose<T>(v: Vec<Vec<T>>) -> Option<Vec<Vec<T>>> {
+    let len = v.first().map_or(0, Vec::len);
+    if v.iter().any(|row| row.len() != len) {
+        return None;
+    }
     let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
     (0..len)
         .map(|_| {
             iters
                 .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
+                .map(|n| n.next())
+                .collect::<Option<Vec<T>>>()
         })
         .collect()
 }
 
-fn read_from_r_to_l_in_columns<T>(v: Vec<Vec<T>>) -> Vec<T> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter().rev()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
-        }).flatten().collect()
+fn decode_operation(content: &str, operation: &str) -> Result<String, AocError> {
+    match operation {
+        "+" | "*" => Ok(operation.to_string()),
+        _ => Err(AocError::parse_at(content, operation, "expected '+' or '*'")),
+    }
 }
 
-fn decode_content_into_puzzle_input(content: String) -> Vec<(String,Vec<i64>)> {
+fn decode_content_into_puzzle_input(content: &str) -> Result<Vec<(String,Vec<i64>)>, AocError> {
     let mut iter = content.trim().rsplit("\n");
 
-    let operations = iter.next().expect("Input should have at least one row")
-        .split_whitespace().map(ToString::to_string);
+    let operations_line = iter.next().unwrap_or_default();
+    let operations = operations_line.split_whitespace()
+        .map(|x| decode_operation(content, x))
+        .collect::<Result<Vec<String>, AocError>>()?;
 
     let values = iter    
                   .map( |x| {
-                      x.trim().split_whitespace()
+                      let row = x.split_whitespace()
                               .map(|y| {
                                   i64::from_str(y)
-                                      .expect("Error casting L1-(N-1) to integers") 
-                              }).collect()
-                  }).collect();
-    let t_values = transpose(values);
-
-    iter::zip(operations, t_values).collect()
-}
+                                      .map_err(|_| AocError::parse_at(content, y, "expected an integer"))
+                              }).collect::<Result<Vec<i64>, AocError>>()?;
+                      if row.len() != operations.len() {
+                          let message = format!("expected {} numbers, found {}", operations.len(), row.len());
+                          return Err(AocError::parse_at(content, x, message));
+                      }
+                      Ok(row)
+                  }).collect::<Result<Vec<Vec<i64>>, AocError>>()?;
+    if values.is_empty() {
+        return Err(AocError::parse_at(content, operations_line, "expected rows of numbers above the operations"));
+    }
+    let t_values = transpose(values)
+        .ok_or_else(|| AocError::parse_at(content, operations_line, "the worksheet is not rectangular"))?;
 
-fn transform_to_problem_input(content: &str) -> (String, Vec<i64>) {
-    let operation = content.chars().last().expect("The input content had less than 1 character.");
-        
-    let values = content.trim_matches('*').trim_matches('+').split_whitespace()
-                          .map(|y| {
-                              i64::from_str(y)
-                                  .expect("Error casting values different from whitespace to integers.")
-                          }).collect();
-    (operation.to_string(), values)
+    Ok(iter::zip(operations, t_values).collect())
 }
 
-fn decode_content_into_puzzle_input_snd(content: String) -> Vec<(String, Vec<i64>)> {
+/// Reads the worksheet column by column from right to left. Each column holds
+/// one number written top to bottom, the operation sits under the leftmost
+/// column of its problem and columns made only of spaces separate problems.
+fn decode_content_into_puzzle_input_snd(content: &str) -> Result<Vec<(String, Vec<i64>)>, AocError> {
+    const RADIX: u32 = 10;
     let lines: Vec<Vec<char>> = content
                     .lines()
                         .map( |l| {
                             l.chars().collect()
                         }).collect();
 
-    let num_lines = lines.len();
-    let content: String = read_from_r_to_l_in_columns(lines).into_iter().collect();
+    let Some((operations, rows)) = lines.split_last() else {
+        return Ok(vec![]);
+    };
+    let operations_line = lines.len();
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let cell = |row: &[char], j: usize| row.get(j).copied().unwrap_or(' ');
+
+    let mut problems = vec![];
+    let mut values = vec![];
+
+    for j in (0..width).rev() {
+        let mut number: Option<i64> = None;
+        for (i, row) in rows.iter().enumerate() {
+            let c = cell(row, j);
+            if c == ' ' {
+                continue;
+            }
+            let digit = c.to_digit(RADIX)
+                .ok_or_else(|| AocError::parse(i + 1, j + 1, &c.to_string(), "expected a digit"))?;
+            let value = number.unwrap_or(0).checked_mul(10)
+                .and_then(|n| n.checked_add(i64::from(digit)))
+                .ok_or_else(|| AocError::parse(i + 1, j + 1, &c.to_string(), "the number is too large"))?;
+            number = Some(value);
+        }
+
+        match (number, cell(operations, j)) {
+            (Some(n), ' ') => values.push(n),
+            (None, ' ') if values.is_empty() => {}
+            (None, ' ') => {
+                return Err(AocError::parse(operations_line, j + 2, " ", "the problem has no operation"));
+            }
+            (number, c @ ('+' | '*')) => {
+                values.extend(number);
+                problems.push((c.to_string(), std::mem::take(&mut values)));
+            }
+            (_, c) => {
+                return Err(AocError::parse(operations_line, j + 1, &c.to_string(), "expected '+' or '*'"));
+            }
+        }
+    }
 
-    let separator_value = std::iter::repeat(" ").take(num_lines).collect::<String>();
-    
-    let problem_lines = content.split(&separator_value);
+    if !values.is_empty() {
+        return Err(AocError::parse(operations_line, 1, " ", "the problem has no operation"));
+    }
 
-    problem_lines.map(|l| transform_to_problem_input(l)).collect()
+    Ok(problems)
 }
 
-fn solve_problem(operation: &str, values: Vec<i64>) -> i64{
+fn solve_problem(operation: &str, values: &[i64]) -> Option<i64>{
     match operation {
-        "+" => values.into_iter().sum(),
-        "*" => values.into_iter().product(),
+        "+" => values.iter().try_fold(0i64, |acc, &x| acc.checked_add(x)),
+        "*" => values.iter().try_fold(1i64, |acc, &x| acc.checked_mul(x)),
         _   => panic!("This operation is not supported")
     }
 }
 
-fn solve_fst(content: String) -> String {
-    let problems = decode_content_into_puzzle_input(content);
-    problems.into_iter().map(|(operation, values)| {solve_problem(&operation, values)}).sum::<i64>().to_string()
+fn solve_worksheet(problems: &[(String, Vec<i64>)]) -> Result<PuzzleOutput, AocError> {
+    let total = problems.iter()
+        .try_fold(0i64, |acc, (operation, values)| {acc.checked_add(solve_problem(operation, values)?)})
+        .ok_or(AocError::Overflow("solving the worksheet"))?;
+    Ok(PuzzleOutput::new(total))
 }
 
-fn solve_snd(content: String) -> String {
-    let problems = decode_content_into_puzzle_input_snd(content);
-    problems.into_iter().map(|(operation, values)| {solve_problem(&operation, values)}).sum::<i64>().to_string()
-}
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Worksheet;
+
+    fn title(&self) -> &'static str {
+        "Trash Compactor"
+    }
 
-pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input {
-        PuzzleInput{
-            iteration: 1,
-            text,
-            ..
-        } => Ok(PuzzleOutput{result: solve_fst(text.to_string())}),
-        PuzzleInput{
-            iteration: 2,
-            text,
-            ..
-        } => Ok(PuzzleOutput{result: solve_snd(text.to_string())}),
-        _ => Err("Incorrect Puzzle Input".to_string())
+    /// Both readings need rows of numbers above a line of operations; the rest
+    /// is checked by the reading of each part.
+    fn parse(&self, content: &str) -> Result<Worksheet, AocError> {
+        let trimmed = content.trim();
+        if trimmed.lines().count() < 2 {
+            return Err(AocError::parse_at(content, trimmed, "expected rows of numbers above the operations"));
+        }
+        Ok(Worksheet { text: content.to_string() })
     }
-} 
+
+    fn part_one(&self, worksheet: &Worksheet, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        solve_worksheet(&worksheet.by_rows()?)
+    }
+
+    fn part_two(&self, worksheet: &Worksheet, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        solve_worksheet(&worksheet.by_columns()?)
+    }
+}
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transform::trace::Silent;
 
     #[test]
     fn decodes_test_input_correctly(){
-        let problems = decode_content_into_puzzle_input("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ".to_string());
+        let problems = decode_content_into_puzzle_input("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ").unwrap();
         assert_eq!(("*".to_string(), vec![6, 45, 123]), problems[0]);
         assert_eq!(("+".to_string(), vec![98, 64, 328]), problems[1]);
         assert_eq!(("*".to_string(), vec![215, 387, 51]), problems[2]);
@@ -129,15 +200,55 @@ mod tests {
 
     #[test]
     fn solves_example_problems(){
-        assert_eq!(33210, solve_problem("*", vec![6, 45, 123]));
-        assert_eq!(490, solve_problem("+", vec![98, 64, 328]));
-        assert_eq!(4243455, solve_problem("*", vec![215, 387, 51]));
-        assert_eq!(401, solve_problem("+", vec![314, 23, 64]));
+        assert_eq!(Some(33210), solve_problem("*", &[6, 45, 123]));
+        assert_eq!(Some(490), solve_problem("+", &[98, 64, 328]));
+        assert_eq!(Some(4243455), solve_problem("*", &[215, 387, 51]));
+        assert_eq!(Some(401), solve_problem("+", &[314, 23, 64]));
     }
 
     #[test]
     fn solves_example_for_fst(){
-        let result = solve_fst("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ".to_string());
-        assert_eq!("4277556", result);
+        let result = Solution.part_one(&Solution.parse("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ").unwrap(), &Silent).unwrap();
+        assert_eq!("4277556", result.answer.to_string());
+    }
+
+    #[test]
+    fn solves_example_for_snd(){
+        let result = Solution.part_two(&Solution.parse("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ").unwrap(), &Silent).unwrap();
+        assert_eq!("3263827", result.answer.to_string());
+    }
+
+    #[test]
+    fn decodes_columns_right_to_left(){
+        let problems = decode_content_into_puzzle_input_snd("123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ").unwrap();
+        assert_eq!(("+".to_string(), vec![4, 431, 623]), problems[0]);
+        assert_eq!(("*".to_string(), vec![356, 24, 1]), problems[3]);
+    }
+
+    #[test]
+    fn decode_reports_location_of_bad_input(){
+        let result = decode_content_into_puzzle_input("123 328\n 45 6x\n*   +");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 5, .. })));
+
+        let result = decode_content_into_puzzle_input("123 328\n 45\n*   +");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 1, .. })));
+
+        let result = decode_content_into_puzzle_input("123 328\n*   -");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 5, .. })));
+
+        let result = decode_content_into_puzzle_input_snd("123 328\n 45 6x\n*   +  ");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 6, .. })));
+
+        let result = decode_content_into_puzzle_input_snd("123 328\n 45 64 \n*   /  ");
+        assert!(matches!(result, Err(AocError::Parse { line: 3, column: 5, .. })));
+    }
+
+    #[test]
+    fn each_part_only_needs_its_own_reading(){
+        let worksheet = Solution.parse("12 -3\n 4  5\n *  +").unwrap();
+        assert_eq!("50", Solution.part_one(&worksheet, &Silent).unwrap().answer.to_string());
+        assert!(matches!(Solution.part_two(&worksheet, &Silent), Err(AocError::Parse { line: 1, column: 4, .. })));
+
+        assert!(matches!(Solution.parse("*   +"), Err(AocError::Parse { line: 1, .. })));
     }
 }
@@ -1,13 +1,18 @@
 use itertools::Itertools;
 
-use crate::{extract::input::PuzzleInput, load::output::PuzzleOutput};
+use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
 
-fn parse_content_into_puzzle_input(content: String) -> Vec<Vec<u32>> {
+fn parse_content_into_puzzle_input(content: &str) -> Result<Vec<Vec<u32>>, AocError> {
     const RADIX: u32 = 10;
     let lines  = content.lines();
-    lines.map(|line| {
+    lines.enumerate().map(|(i, line)| {
         let digits = line.chars();
-        digits.map(|x| {x.to_digit(RADIX).unwrap()}).collect::<Vec<u32>>()
+        digits.enumerate().map(|(j, x)| {
+            x.to_digit(RADIX).ok_or_else(|| AocError::parse(i + 1, j + 1, &x.to_string(), "expected a battery joltage digit"))
+        }).collect::<Result<Vec<u32>, AocError>>()
     }).collect()
 }
 
@@ -20,7 +25,7 @@ fn index_to_leftmost_biggest_digit(xs: &[u32]) -> usize {
     last_index - steps_back_from_the_end_to_the_fst_biggest_digit
 }
 
-fn calculate_largest_joltage_with_n_batteries(bank: &Vec<u32>, n: usize) -> u64 {
+fn calculate_largest_joltage_with_n_batteries(bank: &[u32], n: usize, tracer: &dyn Tracer) -> u64 {
     let length_bank = bank.len();
 
     let mut left = 0;
@@ -32,43 +37,47 @@ fn calculate_largest_joltage_with_n_batteries(bank: &Vec<u32>, n: usize) -> u64
             range_containing_ith_index
         );
         joltage = 10*joltage + u64::from(bank[ith_index]);
+        tracer.detail(format_args!("digit {}: {} at position {} of {}-{}", i + 1, bank[ith_index], ith_index + 1, left + 1, length_bank - n + 1 + i));
         left = ith_index + 1;
     }
     joltage
 }
 
-fn solve_for_n_batteries_turned_on(content: String, n: usize) -> String {
-    let batteries = parse_content_into_puzzle_input(content);
+fn solve_for_n_batteries_turned_on(batteries: &[Vec<u32>], n: usize, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+    if let Some(i) = batteries.iter().position(|bank| bank.len() < n) {
+        let bank: String = batteries[i].iter().map(|x| x.to_string()).collect();
+        return Err(AocError::parse(i + 1, 1, &bank, format!("a bank needs at least {n} batteries")));
+    }
 
-    let joltages: Vec<u64> = batteries.iter().map(|x| calculate_largest_joltage_with_n_batteries(x, n)).collect();
-    joltages.iter().sum::<u64>().to_string()
+    let joltages: Vec<u64> = batteries.iter().enumerate().map(|(i, x)| {
+        let joltage = calculate_largest_joltage_with_n_batteries(x, n, tracer);
+        tracer.step(format_args!("bank {}: {joltage}", i + 1));
+        joltage
+    }).collect();
+    let total = joltages.iter().try_fold(0u64, |acc, &joltage| acc.checked_add(joltage))
+        .ok_or(AocError::Overflow("adding up the joltages"))?;
+    Ok(PuzzleOutput::new(total))
 }
 
-fn solve_fst(content: String) -> PuzzleOutput {
-    PuzzleOutput {
-        result: solve_for_n_batteries_turned_on(content, 2) 
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Vec<Vec<u32>>;
+
+    fn title(&self) -> &'static str {
+        "Lobby"
     }
-}
 
-fn solve_snd(content: String) -> PuzzleOutput {
-    PuzzleOutput{
-        result: solve_for_n_batteries_turned_on(content, 12)
+    fn parse(&self, content: &str) -> Result<Vec<Vec<u32>>, AocError> {
+        parse_content_into_puzzle_input(content)
+    }
+
+    fn part_one(&self, batteries: &Vec<Vec<u32>>, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        solve_for_n_batteries_turned_on(batteries, 2, tracer)
     }
-}
 
-pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input {
-        PuzzleInput {
-            day: 3,
-            iteration: 1,
-            text
-        } => Ok(solve_fst(text.to_string())),
-        PuzzleInput {
-            day: 3,
-            iteration: 2,
-            text
-        } => Ok(solve_snd(text.to_string())),
-        _ => Err("Incorrect Puzzle Input".to_string())
+    fn part_two(&self, batteries: &Vec<Vec<u32>>, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        solve_for_n_batteries_turned_on(batteries, 12, tracer)
     }
 }
 
@@ -76,72 +85,86 @@ pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transform::trace::Silent;
 
     #[test]
     fn fst_passes_input_example(){
-        let result = solve_fst("987654321111111\n811111111111119\n234234234234278\n818181911112111".to_string());
-        assert_eq!(result.result, "357".to_string());
+        let result = Solution.part_one(&Solution.parse("987654321111111\n811111111111119\n234234234234278\n818181911112111").unwrap(), &Silent).unwrap();
+        assert_eq!(result.answer.to_string(), "357".to_string());
+    }
+
+    #[test]
+    fn parse_reports_location_of_bad_digit(){
+        let result = parse_content_into_puzzle_input("987654321111111\n8111111a1111119");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 8, .. })));
+    }
+
+    #[test]
+    fn short_banks_are_rejected(){
+        let batteries = parse_content_into_puzzle_input("987654321111111\n81").unwrap();
+        assert!(Solution.part_one(&batteries, &Silent).is_ok());
+        assert!(matches!(Solution.part_two(&batteries, &Silent), Err(AocError::Parse { line: 2, .. })));
     }
 
     #[test]
     fn fst_correctly_calculates_joltage_first_example(){
         let bank = vec![9,8,7,6,5,4,3,2,1,1,1,1,1,1,1];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 2);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 2, &Silent);
         assert_eq!(result, 98)
     }
 
     #[test]
     fn fst_correctly_calculates_joltage_second_example(){
         let bank = vec![8,1,1,1,1,1,1,1,1,1,1,1,1,1,9];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 2);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 2, &Silent);
         assert_eq!(result, 89)
     }
 
     #[test]
     fn fst_correctly_calculates_joltage_third_example(){
         let bank = vec![2,3,4,2,3,4,2,3,4,2,3,4,2,7,8];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 2);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 2, &Silent);
         assert_eq!(result, 78)
     }
 
     #[test]
     fn fst_correctly_calculates_joltage_fourth_example(){
         let bank = vec![8,1,8,1,8,1,9,1,1,1,1,2,1,1,1];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 2);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 2, &Silent);
         assert_eq!(result, 92)
     }
 
     #[test]
     fn snd_passes_input_example(){
-        let result = solve_snd("987654321111111\n811111111111119\n234234234234278\n818181911112111".to_string());
-        assert_eq!(result.result, "3121910778619".to_string());
+        let result = Solution.part_two(&Solution.parse("987654321111111\n811111111111119\n234234234234278\n818181911112111").unwrap(), &Silent).unwrap();
+        assert_eq!(result.answer.to_string(), "3121910778619".to_string());
     }
 
     #[test]
     fn snd_correctly_calculates_joltage_first_example(){
         let bank = vec![9,8,7,6,5,4,3,2,1,1,1,1,1,1,1];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 12);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 12, &Silent);
         assert_eq!(result, 987654321111)
     }
 
     #[test]
     fn snd_correctly_calculates_joltage_second_example(){
         let bank = vec![8,1,1,1,1,1,1,1,1,1,1,1,1,1,9];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 12);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 12, &Silent);
         assert_eq!(result, 811111111119)
     }
 
     #[test]
     fn snd_correctly_calculates_joltage_third_example(){
         let bank = vec![2,3,4,2,3,4,2,3,4,2,3,4,2,7,8];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 12);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 12, &Silent);
         assert_eq!(result, 434234234278)
     }
 
     #[test]
     fn snd_correctly_calculates_joltage_fourth_example(){
         let bank = vec![8,1,8,1,8,1,9,1,1,1,1,2,1,1,1];
-        let result = calculate_largest_joltage_with_n_batteries(&bank, 12);
+        let result = calculate_largest_joltage_with_n_batteries(&bank, 12, &Silent);
         assert_eq!(result, 888911112111)
     }
     
@@ -1,12 +1,19 @@
-use crate::{extract::input::PuzzleInput, load::output::PuzzleOutput};
+use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::solver::Solver;
+use crate::transform::trace::Tracer;
+
+fn decode_content_into_puzzle_input(content: &str) -> Result<Vec<(i64, i64)>, AocError>{
+    let parse_id = |y: &str| {
+        y.parse::<i64>().map_err(|_| AocError::parse_at(content, y, "expected an integer id"))
+    };
 
-fn decode_content_into_puzzle_input(content: String) -> Vec<(i64, i64)>{
     let ranges = content.trim().split(",");
     ranges.map(|x| {
-        let mut ys = x.trim().split("-");
-        let y0 = ys.next().expect("No first item found").parse::<i64>().expect("Failed to parse into integer.");
-        let y1 = ys.next().expect("No second item found").parse::<i64>().expect("Failed to parse into integer.");
-        (y0, y1)
+        let x = x.trim();
+        let (y0, y1) = x.split_once("-")
+            .ok_or_else(|| AocError::parse_at(content, x, "expected a range like 11-22"))?;
+        Ok((parse_id(y0)?, parse_id(y1)?))
     }).collect()
 }
 
@@ -27,20 +34,6 @@ fn is_valid_id(id: i64) ->  bool{
     }
 }
 
-fn solve_fst(content: String) -> PuzzleOutput {
-    let mut ret = 0;
-    for (y0, y1) in decode_content_into_puzzle_input(content) {
-        for y in y0..y1+1 {
-            if !is_valid_id(y){
-                ret += y;
-            }
-        }
-    }
-    PuzzleOutput{
-        result: ret.to_string()
-    }
-}
-
 fn can_build_id_by_concatenating_seq(id: &str, seq: &str) -> bool{
     let id_chars: Vec<char> = id.chars().collect();
     let seq_chars: Vec<char> = seq.chars().collect();
@@ -74,46 +67,55 @@ fn contains_repeated_sequence(id: i64) -> bool{
     false
 }
 
-fn solve_snd(content: String) -> PuzzleOutput {
-    let mut ret = 0;
-    for (y0, y1) in decode_content_into_puzzle_input(content) {
-        for y in y0..y1+1 {
-            if contains_repeated_sequence(y){
-                ret += y;
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Vec<(i64, i64)>;
+
+    fn title(&self) -> &'static str {
+        "Gift Shop"
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<(i64, i64)>, AocError> {
+        decode_content_into_puzzle_input(content)
+    }
+
+    fn part_one(&self, ranges: &Vec<(i64, i64)>, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let mut ret: i64 = 0;
+        for &(y0, y1) in ranges {
+            for y in y0..=y1 {
+                if !is_valid_id(y){
+                    ret = ret.checked_add(y).ok_or(AocError::Overflow("adding up invalid ids"))?;
+                }
             }
         }
+        Ok(PuzzleOutput::new(ret))
     }
-    PuzzleOutput {
-        result: ret.to_string()
-    }
-}
 
-pub fn solve(input: &PuzzleInput) -> Result<PuzzleOutput, String> {
-    match input {
-        PuzzleInput {
-            day: 2,
-            iteration: 1,
-            text
-        } => Ok(solve_fst(text.to_string())),
-        PuzzleInput {
-            day: 2,
-            iteration: 2,
-            text
-        } => Ok(solve_snd(text.to_string())),
-        _ => Err("Incorrect Puzzle Input".to_string())
+    fn part_two(&self, ranges: &Vec<(i64, i64)>, _tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        let mut ret: i64 = 0;
+        for &(y0, y1) in ranges {
+            for y in y0..=y1 {
+                if contains_repeated_sequence(y){
+                    ret = ret.checked_add(y).ok_or(AocError::Overflow("adding up invalid ids"))?;
+                }
+            }
+        }
+        Ok(PuzzleOutput::new(ret))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transform::trace::Silent;
 
     #[test]
     fn fst_passes_input_sample(){
-        let result = solve_fst("11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
+        let result = Solution.part_one(&Solution.parse("11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
 1698522-1698528,446443-446449,38593856-38593862,565653-565659,
-824824821-824824827,2121212118-2121212124".to_string());
-        assert_eq!(result.result, "1227775554")
+824824821-824824827,2121212118-2121212124").unwrap(), &Silent).unwrap();
+        assert_eq!(result.answer.to_string(), "1227775554")
     }
 
     #[test]
@@ -136,10 +138,19 @@ mod tests {
 
     #[test]
     fn snd_passess_input_sample(){
-        let result = solve_snd("11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
+        let result = Solution.part_two(&Solution.parse("11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
 1698522-1698528,446443-446449,38593856-38593862,565653-565659,
-824824821-824824827,2121212118-2121212124".to_string());
-        assert_eq!(result.result, "4174379265")
+824824821-824824827,2121212118-2121212124").unwrap(), &Silent).unwrap();
+        assert_eq!(result.answer.to_string(), "4174379265")
+    }
+
+    #[test]
+    fn decode_reports_location_of_bad_range(){
+        let result = decode_content_into_puzzle_input("11-22,95-115,\n998-1x12");
+        assert!(matches!(result, Err(AocError::Parse { line: 2, column: 5, .. })));
+
+        let result = decode_content_into_puzzle_input("11-22,95");
+        assert!(matches!(result, Err(AocError::Parse { line: 1, column: 7, .. })));
     }
 
     #[test]
@@ -0,0 +1,95 @@
+use crate::error::AocError;
+use crate::load::output::PuzzleOutput;
+use crate::transform::trace::{Silent, Tracer};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A solution to one day of the calendar. The input text is parsed once into
+/// the day's structured `Input` and each part works on that representation,
+/// telling `tracer` about the steps it takes.
+pub trait Solver {
+    /// Shared between threads when both parts are solved from one parse.
+    type Input: Send + Sync;
+
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, content: &str) -> Result<Self::Input, AocError>;
+
+    fn part_one(&self, input: &Self::Input, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError>;
+
+    fn part_two(&self, input: &Self::Input, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError>;
+}
+
+/// Type-erased view of a `Solver` so days with different `Input` types can
+/// live together in the registry.
+pub trait Puzzle: Sync {
+    fn title(&self) -> &'static str;
+
+    fn parts(&self) -> &'static [Part] {
+        &[Part::One, Part::Two]
+    }
+
+    /// Parses `content` once so any of its parts can be solved afterwards
+    /// without parsing it again.
+    fn parse<'a>(&'a self, content: &str) -> Result<Box<dyn Parsed + 'a>, AocError>;
+
+    fn solve(&self, content: &str, part: Part) -> Result<PuzzleOutput, AocError> {
+        self.parse(content)?.solve(part)
+    }
+}
+
+/// A puzzle input already parsed into the structured type of its day.
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Result<PuzzleOutput, AocError> {
+        self.trace(part, &Silent)
+    }
+
+    /// Solves `part` while reporting its steps to `tracer`.
+    fn trace(&self, part: Part, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError>;
+}
+
+struct ParsedInput<'a, S: Solver> {
+    solver: &'a S,
+    input: S::Input,
+}
+
+impl<S: Solver + Sync> Parsed for ParsedInput<'_, S> {
+    fn trace(&self, part: Part, tracer: &dyn Tracer) -> Result<PuzzleOutput, AocError> {
+        match part {
+            Part::One => self.solver.part_one(&self.input, tracer),
+            Part::Two => self.solver.part_two(&self.input, tracer),
+        }
+    }
+}
+
+impl<S: Solver + Sync> Puzzle for S {
+    fn title(&self) -> &'static str {
+        Solver::title(self)
+    }
+
+    fn parse<'a>(&'a self, content: &str) -> Result<Box<dyn Parsed + 'a>, AocError> {
+        let input = Solver::parse(self, content)?;
+        Ok(Box::new(ParsedInput { solver: self, input }))
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::AocError;
+
+/// How much a tracer is told: the steps that lead to an answer, or also the
+/// details of each step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// One event per step of the puzzle, e.g. per rotation of the dial.
+    Step,
+    /// The choices made within a step, e.g. every digit picked in a bank.
+    Detail,
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Level> {
+        match name {
+            "step" => Some(Level::Step),
+            "detail" => Some(Level::Detail),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Step => write!(f, "step"),
+            Level::Detail => write!(f, "detail"),
+        }
+    }
+}
+
+/// Receives the events solvers emit to explain how they reach an answer.
+/// Solvers call `step` and `detail`; the events are only formatted when the
+/// tracer takes their level.
+pub trait Tracer: Send + Sync {
+    fn enabled(&self, level: Level) -> bool;
+
+    fn event(&self, level: Level, message: fmt::Arguments<'_>);
+
+    fn step(&self, message: fmt::Arguments<'_>) {
+        if self.enabled(Level::Step) {
+            self.event(Level::Step, message);
+        }
+    }
+
+    fn detail(&self, message: fmt::Arguments<'_>) {
+        if self.enabled(Level::Detail) {
+            self.event(Level::Detail, message);
+        }
+    }
+}
+
+/// The tracer of normal runs, which drops every event.
+pub struct Silent;
+
+impl Tracer for Silent {
+    fn enabled(&self, _level: Level) -> bool {
+        false
+    }
+
+    fn event(&self, _level: Level, _message: fmt::Arguments<'_>) {}
+}
+
+/// Writes the events up to `level` as lines, to stderr or a file.
+pub struct Trace {
+    level: Level,
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Trace {
+    pub fn new(level: Level, out: impl Write + Send + 'static) -> Trace {
+        Trace { level, out: Mutex::new(Box::new(out)) }
+    }
+
+    pub fn stderr(level: Level) -> Trace {
+        Trace::new(level, io::stderr())
+    }
+
+    /// Writes to `path`, replacing what it held.
+    pub fn file(level: Level, path: &Path) -> Result<Trace, AocError> {
+        Ok(Trace::new(level, File::create(path)?))
+    }
+}
+
+impl Tracer for Trace {
+    fn enabled(&self, level: Level) -> bool {
+        level <= self.level
+    }
+
+    fn event(&self, level: Level, message: fmt::Arguments<'_>) {
+        // A solver that panicked while writing leaves the lock poisoned;
+        // the writer itself is still usable.
+        let mut out = self.out.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Tracing must never make a run fail, so write errors are ignored.
+        let _ = match level {
+            Level::Step => writeln!(out, "{message}"),
+            Level::Detail => writeln!(out, "  {message}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// A writer whose content stays readable after the tracer took it.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn trace_writes_events_up_to_its_level(){
+        let out = Shared::default();
+        let tracer = Trace::new(Level::Step, out.clone());
+        tracer.step(format_args!("dial at {}", 82));
+        tracer.detail(format_args!("not written"));
+        assert_eq!("dial at 82\n", String::from_utf8(out.0.lock().unwrap().clone()).unwrap());
+
+        let out = Shared::default();
+        let tracer = Trace::new(Level::Detail, out.clone());
+        tracer.step(format_args!("bank 1"));
+        tracer.detail(format_args!("picked 9"));
+        assert_eq!("bank 1\n  picked 9\n", String::from_utf8(out.0.lock().unwrap().clone()).unwrap());
+    }
+
+    #[test]
+    fn levels_are_read_by_name(){
+        assert_eq!(Some(Level::Detail), Level::from_name("detail"));
+        assert_eq!(None, Level::from_name("verbose"));
+        assert!(!Silent.enabled(Level::Step));
+    }
+}
@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::extract::config::{Config, InputSource};
+use crate::extract::input::PuzzleInput;
+
+/// How often the watched files are looked at.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time and length of every watched file, to tell when any
+/// of them changes. Files that disappear or appear count as changes too.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    /// Takes a snapshot of the files of `directories`, one level deep, and of
+    /// `files`.
+    pub fn take(directories: &[PathBuf], files: &[PathBuf]) -> Snapshot {
+        let mut paths: Vec<PathBuf> = files.to_vec();
+        for directory in directories {
+            let entries = fs::read_dir(directory).into_iter().flatten().flatten();
+            paths.extend(entries.map(|entry| entry.path()).filter(|path| path.is_file()));
+        }
+        paths.sort();
+        paths.dedup();
+
+        Snapshot(paths.into_iter().map(|path| {
+            let metadata = fs::metadata(&path).ok();
+            let modified = metadata.as_ref().and_then(|metadata| metadata.modified().ok());
+            let len = metadata.map_or(0, |metadata| metadata.len());
+            (path, modified, len)
+        }).collect())
+    }
+
+    /// The files that differ between the two snapshots.
+    pub fn changes<'a>(&'a self, before: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changed: Vec<&Path> = self.0.iter()
+            .filter(|entry| !before.0.contains(entry))
+            .map(|(path, _, _)| path.as_path())
+            .collect();
+        changed.extend(before.0.iter()
+            .filter(|(path, _, _)| self.0.iter().all(|(other, _, _)| other != path))
+            .map(|(path, _, _)| path.as_path()));
+        changed
+    }
+}
+
+/// The directories and files whose changes re-run `day`: its data directory
+/// with its examples and profiles, and the input file given with `--input`.
+fn watched(config: &Config, day: u8) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let directory = PuzzleInput::day_directory(config, day);
+    let directories = vec![directory.join("inputs"), directory];
+    let files = match &config.input {
+        Some(InputSource::File(path)) => vec![path.clone()],
+        _ => vec![],
+    };
+    (directories, files)
+}
+
+/// Calls `run` once, then again every time the data files of `day` change.
+/// Never returns; the user stops it with Ctrl-C.
+pub fn watch(config: &Config, day: u8, mut run: impl FnMut()) -> ! {
+    let (directories, files) = watched(config, day);
+    let mut snapshot = Snapshot::take(&directories, &files);
+    run();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = Snapshot::take(&directories, &files);
+        if current == snapshot {
+            continue;
+        }
+
+        for path in current.changes(&snapshot) {
+            println!("\n{} changed", path.display());
+        }
+        // Editors often write a file in several steps; wait for them to end.
+        thread::sleep(POLL_INTERVAL / 5);
+        snapshot = Snapshot::take(&directories, &files);
+        run();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_notices_changed_and_new_files(){
+        let directory = std::env::temp_dir().join(format!("aoc-2025-watch-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("input"), "L68\n").unwrap();
+        let directories = vec![directory.clone()];
+
+        let before = Snapshot::take(&directories, &[]);
+        assert_eq!(before, Snapshot::take(&directories, &[]));
+
+        fs::write(directory.join("input"), "L68\nR48\n").unwrap();
+        fs::write(directory.join("test"), "L1\n").unwrap();
+        let after = Snapshot::take(&directories, &[]);
+        assert_eq!(vec![directory.join("input"), directory.join("test")], after.changes(&before));
+
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(2, Snapshot::take(&directories, &[]).changes(&after).len());
+    }
+}
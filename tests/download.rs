@@ -0,0 +1,65 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use aoc_2025::extract::{config::Config, download::Downloader, input::PuzzleInput};
+use aoc_2025::http::{Client, DEFAULT_TIMEOUT, USER_AGENT};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-2025-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn downloads_missing_inputs_once(){
+    let dir = scratch_dir("download");
+    let path = dir.join("day/3/input");
+    let (base_url, server) = common::serve_once(200, "987654321111111\n811111111111119\n");
+
+    assert!(Downloader::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).fetch(2025, 3, &path).unwrap());
+    assert_eq!("987654321111111\n811111111111119\n", fs::read_to_string(&path).unwrap());
+
+    let request = server.join().unwrap();
+    assert_eq!("GET /2025/day/3/input HTTP/1.1", request.line);
+    assert_eq!(Some("session=abc123"), request.header("Cookie"));
+    assert_eq!(Some(USER_AGENT), request.header("User-Agent"));
+    assert!(request.body.is_empty());
+
+    // Nothing listens there any more, so a second request would fail.
+    assert!(!Downloader::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).fetch(2025, 3, &path).unwrap());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_with_download_fills_the_data_directory(){
+    let dir = scratch_dir("download-run");
+    let (base_url, server) = common::serve_once(200, "L68\nL30\nR48\n");
+
+    let args: Vec<String> = ["aoc-2025", "run", "1", "--download", "--base-url", &base_url, "--data-dir"]
+        .iter().map(|arg| arg.to_string())
+        .chain([dir.display().to_string()])
+        .collect();
+    let mut config = Config::new(&args).unwrap();
+    config.session = Some("abc123".to_string());
+
+    let input = PuzzleInput::new(&config).unwrap();
+    assert_eq!("L68\nL30\nR48\n", input.text);
+    assert!(dir.join("2025/day/1/input").is_file());
+    server.join().unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn failed_downloads_leave_no_input_behind(){
+    let dir = scratch_dir("download-failed");
+    let path = dir.join("day/9/input");
+    let (base_url, server) = common::serve_once(404, "Please don't repeatedly request this endpoint before it unlocks!");
+
+    assert!(Downloader::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).fetch(2025, 9, &path).is_err());
+    assert!(!path.exists());
+    server.join().unwrap();
+}
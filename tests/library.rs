@@ -0,0 +1,25 @@
+use aoc_2025::transform::day::{self, one::Movement};
+use aoc_2025::{AocError, Answer, Part};
+
+const EXAMPLE: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+#[test]
+fn solves_text_held_in_memory(){
+    assert_eq!(Answer::Unsigned(3), aoc_2025::solve(1, Part::One, EXAMPLE).unwrap());
+    assert_eq!("6", aoc_2025::solve(1, Part::Two, EXAMPLE).unwrap().to_string());
+    assert!(matches!(aoc_2025::solve(25, Part::One, EXAMPLE), Err(AocError::UnknownDay { year: 2025, day: 25 })));
+    assert!(matches!(aoc_2025::solve_year(2024, 1, Part::One, EXAMPLE), Err(AocError::UnknownDay { year: 2024, day: 1 })));
+    assert_eq!("no solution available for day 1 of 2024", aoc_2025::solve_year(2024, 1, Part::One, EXAMPLE).unwrap_err().to_string());
+}
+
+#[test]
+fn parsed_inputs_are_public(){
+    use aoc_2025::transform::solver::Solver;
+
+    let movements = day::one::Solution.parse("L68\nR48").unwrap();
+    assert_eq!(vec![Movement::Left(68), Movement::Right(48)], movements);
+
+    let parsed = day::parse(2025, 5, "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32").unwrap();
+    assert_eq!(Answer::Unsigned(3), parsed.solve(Part::One).unwrap().answer);
+    assert_eq!(Answer::Unsigned(14), parsed.solve(Part::Two).unwrap().answer);
+}
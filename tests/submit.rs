@@ -0,0 +1,49 @@
+mod common;
+
+use std::time::Duration;
+
+use aoc_2025::error::AocError;
+use aoc_2025::http::{Client, DEFAULT_TIMEOUT, USER_AGENT};
+use aoc_2025::load::{output::{Answer, PuzzleOutput}, submit::{Submitter, Verdict}};
+use aoc_2025::transform::solver::Part;
+
+fn answer(result: &str) -> PuzzleOutput {
+    PuzzleOutput::new(Answer::parse(result))
+}
+
+#[test]
+fn posts_the_answer_with_the_session(){
+    let (base_url, server) = common::serve_once(200, "<main><article><p>That's the right answer!</p></article></main>");
+
+    let verdict = Submitter::new(Client::new(&base_url, "abc123\n", DEFAULT_TIMEOUT)).submit(2025, 5, Part::Two, &answer("14")).unwrap();
+    assert_eq!(Verdict::Correct, verdict);
+
+    let request = server.join().unwrap();
+    assert_eq!("POST /2025/day/5/answer HTTP/1.1", request.line);
+    assert_eq!(Some("session=abc123"), request.header("Cookie"));
+    assert_eq!(Some(USER_AGENT), request.header("User-Agent"));
+    assert_eq!("level=2&answer=14", request.body);
+}
+
+#[test]
+fn reports_wrong_answers(){
+    let (base_url, server) = common::serve_once(200, "<p>That's not the right answer; your answer is too low.</p>");
+    assert_eq!(Verdict::TooLow, Submitter::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).submit(2025, 1, Part::One, &answer("3")).unwrap());
+    server.join().unwrap();
+
+    let (base_url, server) = common::serve_once(200, "<p>You gave an answer too recently.  You have 1m 5s left to wait.</p>");
+    let verdict = Submitter::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).submit(2025, 1, Part::One, &answer("3")).unwrap();
+    assert_eq!(Verdict::RateLimited(Duration::from_secs(65)), verdict);
+    server.join().unwrap();
+}
+
+#[test]
+fn fails_on_pages_without_a_verdict(){
+    let (base_url, server) = common::serve_once(200, "<p>Please log in.</p>");
+    assert!(matches!(Submitter::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).submit(2025, 1, Part::One, &answer("3")), Err(AocError::Http(_))));
+    server.join().unwrap();
+
+    let (base_url, server) = common::serve_once(500, "oops");
+    assert!(matches!(Submitter::new(Client::new(&base_url, "abc123", DEFAULT_TIMEOUT)).submit(2025, 1, Part::One, &answer("3")), Err(AocError::Http(_))));
+    server.join().unwrap();
+}